@@ -19,6 +19,18 @@ class Daily:
     def deinit() -> None:
         ...
 
+    @staticmethod
+    def shutdown(timeout: float = 5.0) -> Sequence[Mapping[str, Any]]:
+        ...
+
+    @staticmethod
+    def metrics_text() -> str:
+        ...
+
+    @staticmethod
+    def serve_metrics(port: int, host: str = "127.0.0.1") -> int:
+        ...
+
     @staticmethod
     def create_camera_device(
             device_name: str,
@@ -54,15 +66,37 @@ class Daily:
     def select_speaker_device(device_name: str) -> None:
         ...
 
+    @staticmethod
+    def list_devices() -> Sequence[Mapping[str, Any]]:
+        ...
+
+    @staticmethod
+    def destroy_device(device_name: str) -> None:
+        ...
+
 
 class CallClient:
 
-    def __init__(self, event_handler: Optional[EventHandler] = None) -> None:
+    def __init__(self,
+                 event_handler: Optional[EventHandler] = None,
+                 weak_event_handler: bool = False) -> None:
         ...
 
     def release(self) -> None:
         ...
 
+    def __enter__(self) -> "CallClient":
+        ...
+
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        ...
+
+    async def __aenter__(self) -> "CallClient":
+        ...
+
+    async def __aexit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        ...
+
     def join(self,
              meeting_url: str,
              meeting_token: Optional[str] = None,
@@ -71,11 +105,30 @@ class CallClient:
              completion: Optional[Callable[[Optional[Mapping[str,
                                                              Any]],
                                             Optional[str]],
-                                           None]] = None) -> None:
+                                           None]] = None,
+             timeout: Optional[float] = None) -> int:
+        ...
+
+    def join_sync(self,
+                  meeting_url: str,
+                  meeting_token: Optional[str] = None,
+                  client_settings: Optional[Mapping[str, Any]] = None,
+                  timeout: Optional[float] = None) -> Mapping[str, Any]:
         ...
 
     def leave(self, completion: Optional[Callable[[
-              Optional[str]], None]] = None) -> None:
+              Optional[str]], None]] = None,
+              timeout: Optional[float] = None) -> int:
+        ...
+
+    def leave_sync(self,
+                   timeout: Optional[float] = None) -> None:
+        ...
+
+    def cancel(self, request_id: int) -> bool:
+        ...
+
+    def id(self) -> int:
         ...
 
     def set_user_name(self, user_name: str) -> None:
@@ -90,13 +143,40 @@ class CallClient:
     def participant_counts(self) -> Mapping[str, Any]:
         ...
 
+    def wait_for_call_state(self, state: str, timeout: Optional[float] = None) -> str:
+        ...
+
+    def wait_for_participant(self,
+                             participant: str | Callable[[Mapping[str, Any]], bool],
+                             timeout: Optional[float] = None) -> Mapping[str, Any]:
+        ...
+
+    def wait_for_track(self,
+                       participant_id: str,
+                       kind: str = "camera",
+                       state: str = "playable",
+                       timeout: Optional[float] = None) -> Mapping[str, Any]:
+        ...
+
     def update_remote_participants(self,
                                    remote_participants: Mapping[str, Any],
-                                   completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                                   completion: Optional[Callable[[Optional[str]], None]] = None,
+                                   timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_remote_participants_sync(self,
+                                        remote_participants: Mapping[str, Any],
+                                        timeout: Optional[float] = None) -> None:
         ...
 
     def eject_remote_participants(
-            self, ids: Sequence[str], completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+            self, ids: Sequence[str], completion: Optional[Callable[[Optional[str]], None]] = None,
+                                  timeout: Optional[float] = None) -> int:
+        ...
+
+    def eject_remote_participants_sync(self,
+                                       ids: Sequence[str],
+                                       timeout: Optional[float] = None) -> None:
         ...
 
     def inputs(self) -> Mapping[str, Any]:
@@ -104,7 +184,26 @@ class CallClient:
 
     def update_inputs(self,
                       input_settings: Mapping[str, Any],
-                      completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                      completion: Optional[Callable[[Optional[str]], None]] = None,
+                      timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_inputs_sync(self,
+                           input_settings: Mapping[str, Any],
+                           timeout: Optional[float] = None) -> None:
+        ...
+
+    def set_devices(self,
+                    camera: Optional[str] = None,
+                    microphone: Optional[str] = None,
+                    completion: Optional[Callable[[Optional[str]], None]] = None,
+                    timeout: Optional[float] = None) -> int:
+        ...
+
+    def set_devices_sync(self,
+                         camera: Optional[str] = None,
+                         microphone: Optional[str] = None,
+                         timeout: Optional[float] = None) -> None:
         ...
 
     def publishing(self) -> Mapping[str, Any]:
@@ -112,7 +211,13 @@ class CallClient:
 
     def update_publishing(self,
                           publishing_settings: Mapping[str, Any],
-                          completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                          completion: Optional[Callable[[Optional[str]], None]] = None,
+                          timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_publishing_sync(self,
+                               publishing_settings: Mapping[str, Any],
+                               timeout: Optional[float] = None) -> None:
         ...
 
     def subscriptions(self) -> Mapping[str, Any]:
@@ -121,7 +226,14 @@ class CallClient:
     def update_subscriptions(self,
                              participant_settings: Optional[Mapping[str, Any]] = None,
                              profile_settings: Optional[Mapping[str, Any]] = None,
-                             completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                             completion: Optional[Callable[[Optional[str]], None]] = None,
+                             timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_subscriptions_sync(self,
+                                  participant_settings: Optional[Mapping[str, Any]] = None,
+                                  profile_settings: Optional[Mapping[str, Any]] = None,
+                                  timeout: Optional[float] = None) -> None:
         ...
 
     def subscription_profiles(self) -> Mapping[str, Any]:
@@ -131,72 +243,208 @@ class CallClient:
                                      profile_settings: Mapping[str,
                                                                Any],
                                      completion: Optional[Callable[[Optional[str]],
-                                                                   None]] = None) -> None:
+                                                                   None]] = None,
+                                     timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_subscription_profiles_sync(self,
+                                          profile_settings: Mapping[str, Any],
+                                          timeout: Optional[float] = None) -> None:
         ...
 
     def update_permissions(self,
                            permissions: Mapping[str, Any],
-                           completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                           completion: Optional[Callable[[Optional[str]], None]] = None,
+                           timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_permissions_sync(self,
+                                permissions: Mapping[str, Any],
+                                timeout: Optional[float] = None) -> None:
+        ...
+
+    def recordings(self) -> Mapping[str, Any]:
+        ...
+
+    def live_streams(self) -> Mapping[str, Any]:
         ...
 
     def start_recording(self,
                         streaming_settings: Optional[Mapping[str, Any]] = None,
                         stream_id: Optional[str] = None,
                         force_new: Optional[bool] = None,
-                        completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                        completion: Optional[Callable[[Optional[str]], None]] = None,
+                        timeout: Optional[float] = None) -> int:
+        ...
+
+    def start_recording_sync(self,
+                             streaming_settings: Optional[Mapping[str, Any]] = None,
+                             stream_id: Optional[str] = None,
+                             force_new: Optional[bool] = None,
+                             timeout: Optional[float] = None) -> None:
         ...
 
     def stop_recording(self,
                        stream_id: Optional[str] = None,
-                       completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                       completion: Optional[Callable[[Optional[str]], None]] = None,
+                       timeout: Optional[float] = None) -> int:
+        ...
+
+    def stop_recording_sync(self,
+                            stream_id: Optional[str] = None,
+                            timeout: Optional[float] = None) -> None:
         ...
 
     def update_recording(self,
                          update_settings: Optional[Mapping[str, Any]] = None,
                          stream_id: Optional[str] = None,
-                         completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                         completion: Optional[Callable[[Optional[str]], None]] = None,
+                         timeout: Optional[float] = None) -> int:
+        ...
+
+    def update_recording_sync(self,
+                              update_settings: Optional[Mapping[str, Any]] = None,
+                              stream_id: Optional[str] = None,
+                              timeout: Optional[float] = None) -> None:
         ...
 
     def start_transcription(self,
                             settings: Optional[Mapping[str, Any]] = None,
-                            completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                            completion: Optional[Callable[[Optional[str]], None]] = None,
+                            timeout: Optional[float] = None) -> int:
+        ...
+
+    def start_transcription_sync(self,
+                                 settings: Optional[Mapping[str, Any]] = None,
+                                 timeout: Optional[float] = None) -> None:
         ...
 
     def stop_transcription(
-            self, completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+            self, completion: Optional[Callable[[Optional[str]], None]] = None,
+                           timeout: Optional[float] = None) -> int:
+        ...
+
+    def stop_transcription_sync(self,
+                                timeout: Optional[float] = None) -> None:
+        ...
+
+    def transcript(self) -> Sequence[Mapping[str, Any]]:
+        ...
+
+    def export_transcript(self, format: str = "json") -> str:
+        ...
+
+    def clear_transcript(self) -> None:
         ...
 
     def start_dialout(self,
                       settings: Optional[Mapping[str, Any]] = None,
-                      completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                      completion: Optional[Callable[[Optional[str]], None]] = None,
+                      timeout: Optional[float] = None) -> int:
+        ...
+
+    def start_dialout_sync(self,
+                           settings: Optional[Mapping[str, Any]] = None,
+                           timeout: Optional[float] = None) -> None:
+        ...
+
+    def stop_dialout(self,
+                     participant: str,
+                     completion: Optional[Callable[[Optional[str]], None]] = None,
+                     timeout: Optional[float] = None) -> int:
         ...
 
-    def stop_dialout(self, completion: Optional[Callable[[
-                     Optional[str]], None]] = None) -> None:
+    def stop_dialout_sync(self,
+                          participant: str,
+                          timeout: Optional[float] = None) -> None:
         ...
 
     def send_app_message(self,
                          message: Any,
                          participant: Optional[str] = None,
-                         completion: Optional[Callable[[Optional[str]], None]] = None) -> None:
+                         completion: Optional[Callable[[Optional[str]], None]] = None,
+                         timeout: Optional[float] = None,
+                         topic: Optional[str] = None) -> int:
+        ...
+
+    def send_app_message_sync(self,
+                              message: Any,
+                              participant: Optional[str] = None,
+                              timeout: Optional[float] = None,
+                              topic: Optional[str] = None) -> None:
+        ...
+
+    def send_large_app_message(self,
+                               message: Any,
+                               participant: Optional[str] = None,
+                               chunk_size: int = 3000,
+                               completion: Optional[Callable[[Optional[str]], None]] = None,
+                               topic: Optional[str] = None) -> str:
+        ...
+
+    def request_app_message(self,
+                            message: Any,
+                            participant: str,
+                            method: Optional[str] = None,
+                            timeout: float = 10.0) -> Any:
+        ...
+
+    def register_app_message_handler(self,
+                                     method: str,
+                                     handler: Optional[Callable[[Any, str], Any]]) -> None:
+        ...
+
+    def subscribe_app_messages(self, topic: str, callback: Callable[[Any, str], None]) -> int:
+        ...
+
+    def unsubscribe_app_messages(self, subscription_id: int) -> bool:
         ...
 
     def send_prebuilt_chat_message(self,
                                    message: str,
                                    user_name: Optional[str] = None,
                                    completion: Optional[Callable[[Optional[str]],
-                                                                 None]] = None) -> None:
+                                                                 None]] = None,
+                                   timeout: Optional[float] = None) -> int:
+        ...
+
+    def send_prebuilt_chat_message_sync(self,
+                                        message: str,
+                                        user_name: Optional[str] = None,
+                                        timeout: Optional[float] = None) -> None:
+        ...
+
+    def suppress_prebuilt_chat_app_messages(self, suppress: bool = True) -> None:
         ...
 
     def get_network_stats(self) -> Mapping[str, Any]:
         ...
 
+    def network_stats_history(self, window_secs: float = 60.0) -> Mapping[str, Any]:
+        ...
+
+    def network_quality(self) -> str:
+        ...
+
+    def set_network_quality_thresholds(self, thresholds: Mapping[str, Any]) -> None:
+        ...
+
+    def set_reconnect_policy(self, policy: Optional[Mapping[str, Any]]) -> None:
+        ...
+
     def set_audio_renderer(self,
                            participant_id: str,
                            callback: Callable[[str, AudioData], None],
                            audio_source: str = "microphone") -> None:
         ...
 
+    def set_audio_level_observer(self,
+                                 participant_id: str,
+                                 callback: Optional[Callable[[str, Mapping[str, Any]], None]],
+                                 interval_ms: int = 100,
+                                 audio_source: str = "microphone") -> None:
+        ...
+
     def set_video_renderer(self,
                            participant_id: str,
                            callback: Callable[[str, VideoFrame], None],
@@ -204,6 +452,25 @@ class CallClient:
                            color_format: str = "RGBA") -> None:
         ...
 
+    def set_av_renderer(self,
+                        participant_id: str,
+                        callback: Callable[[str, Optional[VideoFrame], Optional[AudioData]], None],
+                        audio_source: str = "microphone",
+                        video_source: str = "camera",
+                        color_format: str = "RGBA") -> None:
+        ...
+
+    def start_local_video_recording(self,
+                                    participant_id: str,
+                                    path: str,
+                                    video_source: str = "camera") -> None:
+        ...
+
+    def stop_local_video_recording(self,
+                                   participant_id: str,
+                                   video_source: str = "camera") -> int:
+        ...
+
 
 class EventHandler:
 
@@ -246,6 +513,15 @@ class EventHandler:
                                message: Mapping[str, Any]) -> None:
         ...
 
+    def on_local_video_recording_error(self,
+                                       participant_id: str,
+                                       video_source: str,
+                                       error: str) -> None:
+        ...
+
+    def on_network_quality_changed(self, quality: str, reasons: Sequence[str]) -> None:
+        ...
+
     def on_network_stats_updated(self, stats: Mapping[str, Any]) -> None:
         ...
 
@@ -263,10 +539,26 @@ class EventHandler:
     def on_participant_updated(self, participant: Mapping[str, Any]) -> None:
         ...
 
+    def on_prebuilt_chat_message(self,
+                                 message: str,
+                                 user_name: Optional[str],
+                                 sender_id: str,
+                                 timestamp: float) -> None:
+        ...
+
     def on_publishing_updated(
             self, publishing_settings: Mapping[str, Any]) -> None:
         ...
 
+    def on_reconnect_failed(self, attempts: int, reason: str) -> None:
+        ...
+
+    def on_reconnected(self, attempts: int) -> None:
+        ...
+
+    def on_reconnecting(self, attempt: int, reason: str, delay: float) -> None:
+        ...
+
     def on_recording_error(self,
                            stream_id: Mapping[str, Any],
                            message: Mapping[str, Any]) -> None:
@@ -303,6 +595,14 @@ class EventHandler:
 
 class AudioData:
 
+    def __init__(self,
+                 audio_frames: bytes,
+                 sample_rate: int = 16000,
+                 num_channels: int = 1,
+                 timestamp_us: int = 0,
+                 sample_offset: int = 0) -> None:
+        ...
+
     @ property
     def bits_per_sample(self) -> int:
         ...
@@ -323,6 +623,47 @@ class AudioData:
     def audio_frames(self) -> bytes:
         ...
 
+    @ property
+    def timestamp_us(self) -> int:
+        ...
+
+    @ property
+    def sample_offset(self) -> int:
+        ...
+
+    @ property
+    def duration_ms(self) -> float:
+        ...
+
+    @ property
+    def level(self) -> Mapping[str, Any]:
+        ...
+
+    def to_float32(self) -> bytes:
+        ...
+
+    def resample(self, sample_rate: int) -> AudioData:
+        ...
+
+    def to_mono(self) -> AudioData:
+        ...
+
+
+class AudioResampler:
+
+    def __init__(self, sample_rate: int) -> None:
+        ...
+
+    @ property
+    def sample_rate(self) -> int:
+        ...
+
+    def resample(self, audio_data: AudioData) -> AudioData:
+        ...
+
+    def reset(self) -> None:
+        ...
+
 
 class VideoFrame:
 
@@ -368,6 +709,9 @@ class VirtualCameraDevice:
     def write_frame(self, frame: bytes) -> None:
         ...
 
+    def close(self) -> None:
+        ...
+
 
 class VirtualMicrophoneDevice:
 
@@ -383,11 +727,18 @@ class VirtualMicrophoneDevice:
     def channels(self) -> int:
         ...
 
+    @ property
+    def level(self) -> Mapping[str, Any]:
+        ...
+
     def write_frames(self,
                      frame: bytes,
                      completion: Optional[Callable[[int], None]] = None) -> int:
         ...
 
+    def close(self) -> None:
+        ...
+
 
 class VirtualSpeakerDevice:
 
@@ -403,11 +754,18 @@ class VirtualSpeakerDevice:
     def channels(self) -> int:
         ...
 
+    @ property
+    def level(self) -> Mapping[str, Any]:
+        ...
+
     def read_frames(self,
                     num_frame: int,
                     completion: Optional[Callable[[bytes], None]] = None) -> bytes:
         ...
 
+    def close(self) -> None:
+        ...
+
 
 class NativeVad:
 
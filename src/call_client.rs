@@ -1,20 +1,50 @@
+pub(crate) mod app_message_chunks;
+pub(crate) mod app_message_rpc;
+pub(crate) mod app_message_topics;
 pub(crate) mod delegate;
 pub(crate) mod event;
 pub(crate) mod event_handler;
+pub(crate) mod network_quality;
+pub(crate) mod network_stats;
+pub(crate) mod prebuilt_chat;
+pub(crate) mod reconnect;
+pub(crate) mod streaming;
+pub(crate) mod transcript;
+pub(crate) mod waiter;
 
 pub(crate) use event_handler::PyEventHandler;
 
+use event_handler::EventHandlerRef;
+
+use app_message_chunks::ChunkAssembler;
+use app_message_rpc::{AppMessageRpc, RpcMessage, RpcResult};
+use app_message_topics::AppMessageTopics;
 use delegate::*;
+use network_quality::{NetworkQualityMonitor, NetworkQualityThresholds};
+use network_stats::NetworkStatsHistory;
+use reconnect::{JoinParams, ReconnectPolicy, Reconnector, RendererRegistration};
+use streaming::StreamingTracker;
+use transcript::{TranscriptAssembler, TranscriptFormat};
+use waiter::{ParticipantMatcher, WaitCondition, Waiter};
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     ffi::{CStr, CString},
-    ptr,
+    process, ptr,
     str::FromStr,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
-use pyo3::{exceptions, prelude::*};
+use pyo3::{
+    exceptions,
+    prelude::*,
+    types::{PyCFunction, PyDict, PyTuple},
+};
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -22,7 +52,18 @@ use webrtc_daily::sys::color_format::ColorFormat;
 
 use daily_core::prelude::*;
 
-use crate::{util::dict::DictValue, GLOBAL_CONTEXT};
+use crate::{
+    media::video_recorder::VideoRecorder,
+    metrics::{CallClientMetrics, CallClientMetricsEntry},
+    util::{dict::DictValue, timer::Timer},
+    GLOBAL_CONTEXT,
+};
+
+// How often blocking requests check for signals (e.g. Ctrl-C) while waiting.
+const SYNC_REQUEST_SIGNALS_INTERVAL: Duration = Duration::from_millis(100);
+
+// How long to wait for the meeting to be left when exiting a `with` block.
+pub(crate) const EXIT_LEAVE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Clone)]
 struct CallClientPtr {
@@ -41,44 +82,805 @@ impl CallClientPtr {
 
 unsafe impl Send for CallClientPtr {}
 
+/// The native call client and its delegate context. This is shared with the
+/// global context which keeps a registry of all the live call clients, so they
+/// can be released when the SDK is deinitialized.
+pub(crate) struct CallClientHandle {
+    id: u64,
+    // The ID of the process that created the call client.
+    pid: u32,
+    call_client: Mutex<Option<CallClientPtr>>,
+    // Set before taking the call client lock to release the call client.
+    releasing: AtomicBool,
+    inner: Arc<PyCallClientInner>,
+    delegate_ctx_ptr: DelegateContextPtr,
+}
+
+impl CallClientHandle {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn check_released(&self) -> PyResult<CallClientPtr> {
+        // The native call client threads don't exist in forked children.
+        if self.pid != process::id() {
+            return Err(exceptions::PyRuntimeError::new_err(
+                "this object was created in a parent process and can't be used after forking",
+            ));
+        }
+
+        // If we have already been released throw an exception.
+        if let Some(call_client) = self.call_client.lock().unwrap().as_ref() {
+            Ok(call_client.clone())
+        } else {
+            Err(exceptions::PyRuntimeError::new_err(
+                "this object has already been released",
+            ))
+        }
+    }
+
+    pub(crate) fn meeting_url(&self) -> Option<String> {
+        self.inner.meeting_url.lock().unwrap().clone()
+    }
+
+    pub(crate) fn metrics(&self) -> CallClientMetricsEntry {
+        CallClientMetricsEntry {
+            client_id: self.id,
+            meeting_url: self.meeting_url(),
+            metrics: self.inner.metrics.snapshot(),
+        }
+    }
+
+    pub(crate) fn is_joined(&self) -> bool {
+        let call_state = self.inner.call_state.lock().unwrap();
+        call_state.as_str() == "joining" || call_state.as_str() == "joined"
+    }
+
+    /// Leaves the meeting. The returned receiver gets the leave result once
+    /// the request completes.
+    pub(crate) fn leave(&self) -> Option<mpsc::Receiver<CompletionResult>> {
+        let mut call_client = self.check_released().ok()?;
+
+        self.inner.reconnector.lock().unwrap().leaving();
+
+        let (sender, receiver) = mpsc::channel();
+
+        let request_id = GLOBAL_CONTEXT.next_request_id();
+
+        self.inner
+            .completions
+            .lock()
+            .unwrap()
+            .insert(request_id, PyCallClientCompletion::Channel(sender));
+
+        unsafe {
+            daily_core_call_client_leave(call_client.as_mut(), request_id);
+        }
+
+        Some(receiver)
+    }
+
+    /// Drops all references to the native call client without destroying
+    /// it. This is used in forked children, where the native call client
+    /// belongs to the parent process.
+    pub(crate) fn forget(&self) {
+        let mut call_client = self.call_client.lock().unwrap();
+
+        if call_client.take().is_some() {
+            {
+                let mut delegates = self.inner.delegates.lock().unwrap();
+                delegates.on_audio_data.take();
+                delegates.on_video_frame.take();
+                delegates.on_event.take();
+            }
+
+            self.inner.event_handler_callback.lock().unwrap().take();
+            self.inner.waiters.lock().unwrap().clear();
+            self.inner.app_message_rpc.lock().unwrap().clear();
+            self.inner.app_message_topics.lock().unwrap().clear();
+
+            GLOBAL_CONTEXT.unregister_call_client(self.id);
+        }
+    }
+
+    /// Joins the meeting again after being disconnected. The result is handled
+    /// by the reconnect policy.
+    pub(crate) fn rejoin(&self, join_params: &JoinParams) {
+        let Ok(mut call_client) = self.check_released() else {
+            return;
+        };
+
+        let meeting_url_cstr =
+            CString::new(join_params.meeting_url.as_str()).expect("invalid meeting URL string");
+        let meeting_token_cstr = join_params
+            .meeting_token
+            .as_ref()
+            .map(|token| CString::new(token.as_str()).expect("invalid meeting token string"));
+        let client_settings_cstr = join_params.client_settings.as_ref().map(|settings| {
+            CString::new(settings.as_str()).expect("invalid client settings string")
+        });
+
+        let request_id = GLOBAL_CONTEXT.next_request_id();
+
+        self.inner
+            .completions
+            .lock()
+            .unwrap()
+            .insert(request_id, PyCallClientCompletion::Reconnect);
+
+        unsafe {
+            daily_core_call_client_join(
+                call_client.as_mut(),
+                request_id,
+                meeting_url_cstr.as_ptr(),
+                meeting_token_cstr
+                    .as_ref()
+                    .map_or(ptr::null_mut(), |s| s.as_ptr()),
+                client_settings_cstr
+                    .as_ref()
+                    .map_or(ptr::null_mut(), |s| s.as_ptr()),
+            );
+        }
+    }
+
+    // Returns the native call client unless it's been released. This is used
+    // from the event thread, so it can't wait for the lock while we are being
+    // released, which waits for the event thread. Otherwise, the lock is only
+    // held for a moment.
+    fn try_call_client(&self) -> Option<CallClientPtr> {
+        loop {
+            if let Ok(call_client) = self.call_client.try_lock() {
+                return call_client.clone();
+            }
+
+            if self.releasing.load(Ordering::SeqCst) {
+                return None;
+            }
+
+            thread::yield_now();
+        }
+    }
+
+    /// Sends an app message from the event thread (e.g. to reply to a
+    /// request). Returns whether the message was sent, which is not the case
+    /// if the call client is being released.
+    pub(crate) fn send_app_message(&self, message: &Value, participant: Option<&str>) -> bool {
+        let Some(mut call_client) = self.try_call_client() else {
+            return false;
+        };
+
+        let message_string = serde_json::to_string(message).unwrap();
+        let message_cstr = CString::new(message_string).expect("invalid message string");
+
+        let participant_cstr =
+            participant.map(|p| CString::new(p).expect("invalid participant string"));
+
+        let request_id = GLOBAL_CONTEXT.next_request_id();
+
+        unsafe {
+            daily_core_call_client_send_app_message(
+                call_client.as_mut(),
+                request_id,
+                message_cstr.as_ptr(),
+                participant_cstr
+                    .as_ref()
+                    .map_or(ptr::null(), |s| s.as_ptr()),
+            );
+        }
+
+        true
+    }
+
+    /// Sets all the registered audio and video renderers again, since they
+    /// don't survive leaving the meeting.
+    pub(crate) fn restore_renderers(&self) {
+        let Some(mut call_client) = self.try_call_client() else {
+            return;
+        };
+
+        let mut registrations: Vec<(u64, RendererRegistration)> = self
+            .inner
+            .renderer_registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(renderer_id, registration)| (*renderer_id, registration.clone()))
+            .collect();
+
+        // Renderers set later replace earlier ones for the same source, so
+        // set them in the same order.
+        registrations.sort_by_key(|(renderer_id, _)| *renderer_id);
+
+        for (renderer_id, registration) in registrations {
+            set_native_renderer(&mut call_client, renderer_id, registration);
+        }
+    }
+
+    /// Leaves the meeting (if joined), waiting up to `timeout` for the leave
+    /// to complete, and then releases the call client. This does nothing if
+    /// the call client has already been released.
+    pub(crate) fn leave_and_release(&self, py: Python<'_>, timeout: Duration) -> PyResult<()> {
+        if self.check_released().is_err() {
+            return Ok(());
+        }
+
+        if self.is_joined() {
+            if let Some(receiver) = self.leave() {
+                // Leave completions need the GIL.
+                let _ = py.allow_threads(move || receiver.recv_timeout(timeout));
+            }
+        }
+
+        self.release(py)
+    }
+
+    pub(crate) fn release(&self, py: Python<'_>) -> PyResult<()> {
+        if self.pid != process::id() {
+            self.forget();
+            return Ok(());
+        }
+
+        // Let the event thread know it shouldn't wait for the lock.
+        self.releasing.store(true, Ordering::SeqCst);
+
+        // Hold the call client lock for the whole function so no one else can
+        // grab it while we are releasing.
+        let mut call_client = self.call_client.lock().unwrap();
+
+        // Make sure we don't try to rejoin.
+        self.inner.reconnector.lock().unwrap().leaving();
+
+        // If we have already been released throw an exception.
+        if call_client.is_none() {
+            return Err(exceptions::PyRuntimeError::new_err(
+                "this object has already been released",
+            ));
+        }
+
+        {
+            // Cleanup video/audio delegates so they are not called during
+            // destroy. Do it inside a new scope so the lock gets released.
+            //
+            // Note that we don't cleanup the event delegate because we might be
+            // waiting on completions to finish (e.g. leave).
+            let mut delegates = self.inner.delegates.lock().unwrap();
+            delegates.on_audio_data.take();
+            delegates.on_video_frame.take();
+        }
+
+        let mut call_client_cpy = call_client.as_ref().unwrap().clone();
+
+        // Here we release the GIL so we can allow any event delegates to
+        // finish. The event delegates will be waiting on the GIL and
+        // execute at this point. But since we just cleanup the delegates
+        // above, the events will actually be a no-op.
+        py.allow_threads(move || unsafe {
+            daily_core_call_client_destroy(call_client_cpy.as_ptr());
+        });
+
+        // Remove any reference to the Python's event handler. This should get
+        // rid of any circular dependency.
+        self.inner.event_handler_callback.lock().unwrap().take();
+
+        // Wake up anyone waiting for a condition or a response.
+        self.inner.waiters.lock().unwrap().clear();
+        self.inner.app_message_rpc.lock().unwrap().clear();
+        self.inner.app_message_topics.lock().unwrap().clear();
+
+        // Make sure local recordings are flushed to disk, without holding the
+        // GIL.
+        let recorders: Vec<_> = self
+            .inner
+            .video_recorders
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, recorder)| recorder)
+            .collect();
+        py.allow_threads(move || {
+            for recorder in recorders {
+                let _ = recorder.finish();
+            }
+        });
+
+        // Cleanup the delegate context. The delegate context still has one
+        // reference count (because of we drop it but increase it again every
+        // time a delegate happens). After the client is destroyed it is safe to
+        // simply get rid of it.
+        let _delegate_ctx = unsafe { Arc::from_raw(self.delegate_ctx_ptr.ptr) };
+
+        // Release the call client pointer. We won't need it anymore.
+        *call_client = None;
+
+        GLOBAL_CONTEXT.unregister_call_client(self.id);
+
+        Ok(())
+    }
+}
+
+// Sets the given native audio or video renderer.
+fn set_native_renderer(
+    call_client: &mut CallClientPtr,
+    renderer_id: u64,
+    registration: RendererRegistration,
+) {
+    let request_id = GLOBAL_CONTEXT.next_request_id();
+
+    match registration {
+        RendererRegistration::Audio {
+            participant_id,
+            audio_source,
+        } => {
+            let participant_cstr =
+                CString::new(participant_id).expect("invalid participant ID string");
+            let audio_source_cstr =
+                CString::new(audio_source).expect("invalid audio source string");
+
+            unsafe {
+                daily_core_call_client_set_participant_audio_renderer(
+                    call_client.as_mut(),
+                    request_id,
+                    renderer_id,
+                    participant_cstr.as_ptr(),
+                    audio_source_cstr.as_ptr(),
+                );
+            }
+        }
+        RendererRegistration::Video {
+            participant_id,
+            video_source,
+            color_format,
+        } => {
+            let participant_cstr =
+                CString::new(participant_id).expect("invalid participant ID string");
+            let video_source_cstr =
+                CString::new(video_source).expect("invalid video source string");
+            let color_format_cstr =
+                CString::new(color_format).expect("invalid color format string");
+
+            unsafe {
+                daily_core_call_client_set_participant_video_renderer(
+                    call_client.as_mut(),
+                    request_id,
+                    renderer_id,
+                    participant_cstr.as_ptr(),
+                    video_source_cstr.as_ptr(),
+                    color_format_cstr.as_ptr(),
+                );
+            }
+        }
+    }
+}
+
 /// This class represents a call client. A call client is a participant of a
 /// Daily meeting and it can receive audio and video from other participants in
 /// the meeting as well as send audio and video. Multiple instances of call
 /// clients can be created in the same application.
 ///
+/// Call clients can be used as context managers (`with` and `async with`), in
+/// which case the meeting is left and the call client released on exit.
+///
 /// :param class event_handler: A subclass of :class:`daily.EventHandler`
+/// :param bool weak_event_handler: If True, only a weak reference to the event handler is kept. This avoids reference cycles when the event handler holds the call client, but the event handler needs to be kept alive elsewhere
 #[pyclass(name = "CallClient", module = "daily")]
 pub struct PyCallClient {
-    call_client: Mutex<Option<CallClientPtr>>,
+    handle: Arc<CallClientHandle>,
     inner: Arc<PyCallClientInner>,
-    delegate_ctx_ptr: DelegateContextPtr,
 }
 
-impl PyCallClient {
-    fn check_released(&self) -> PyResult<CallClientPtr> {
-        // If we have already been released throw an exception.
-        if let Some(call_client) = self.call_client.lock().unwrap().as_ref() {
-            Ok(call_client.clone())
+impl PyCallClient {
+    fn check_released(&self) -> PyResult<CallClientPtr> {
+        self.handle.check_released()
+    }
+
+    fn wait_for(
+        &self,
+        py: Python<'_>,
+        condition: WaitCondition,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        let timeout = timeout_from_secs(timeout)?;
+
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let (sender, receiver) = mpsc::channel();
+
+        let waiter_id = GLOBAL_CONTEXT.next_request_id();
+
+        // Register the waiter before checking the current state so we don't
+        // miss any event in between.
+        self.inner.waiters.lock().unwrap().insert(
+            waiter_id,
+            Waiter {
+                condition: condition.clone(),
+                sender,
+            },
+        );
+
+        let call_state = self.inner.call_state.lock().unwrap().clone();
+        let participants = if condition.needs_participants() {
+            unsafe { get_participants(call_client.as_mut()) }
+        } else {
+            Value::Null
+        };
+
+        let result = if let Some(result) = condition.check_current(&call_state, &participants) {
+            Ok(result)
+        } else {
+            Self::wait_for_waiter(py, receiver, timeout)
+        };
+
+        self.inner.waiters.lock().unwrap().remove(&waiter_id);
+
+        result.map(|result| DictValue(result).to_object(py))
+    }
+
+    // Waits (without holding the GIL) for the result of a waiter. Signals are
+    // checked while waiting, so waiting stops if a signal handler raises an
+    // exception (e.g. `KeyboardInterrupt`).
+    fn wait_for_waiter(
+        py: Python<'_>,
+        mut receiver: mpsc::Receiver<Value>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Value> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let interval = deadline.map_or(SYNC_REQUEST_SIGNALS_INTERVAL, |deadline| {
+                deadline
+                    .saturating_duration_since(Instant::now())
+                    .min(SYNC_REQUEST_SIGNALS_INTERVAL)
+            });
+
+            let (waited_receiver, result) = py.allow_threads(move || {
+                let result = receiver.recv_timeout(interval);
+                (receiver, result)
+            });
+            receiver = waited_receiver;
+
+            match result {
+                Ok(result) => return Ok(result),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(exceptions::PyTimeoutError::new_err(
+                            "condition was not met in time",
+                        ));
+                    }
+
+                    py.check_signals()?;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(exceptions::PyRuntimeError::new_err(
+                        "this object was released while waiting",
+                    ))
+                }
+            }
+        }
+    }
+
+    // Remembers a native renderer so it can be restored after reconnecting.
+    fn register_renderer(&self, renderer_id: u64, registration: RendererRegistration) {
+        // A native audio renderer replaces the previous one of the same audio
+        // source, so audio level observers need to move to the new one.
+        if let RendererRegistration::Audio {
+            participant_id,
+            audio_source,
+        } = &registration
+        {
+            self.inner
+                .remove_audio_sample_offsets(|previous| match previous {
+                    RendererRegistration::Audio {
+                        participant_id: p,
+                        audio_source: s,
+                    } => p == participant_id && s == audio_source,
+                    _ => false,
+                });
+
+            let mut observers = self.inner.audio_level_observers.lock().unwrap();
+
+            let observer_ids: Vec<u64> = observers
+                .iter()
+                .filter(|(_, observer)| {
+                    observer.participant_id == *participant_id
+                        && observer.audio_source == *audio_source
+                })
+                .map(|(observer_id, _)| *observer_id)
+                .collect();
+
+            for observer_id in observer_ids {
+                if let Some(observer) = observers.remove(&observer_id) {
+                    observers.insert(renderer_id, observer);
+                }
+            }
+        }
+
+        self.inner
+            .renderer_registrations
+            .lock()
+            .unwrap()
+            .insert(renderer_id, registration);
+    }
+
+    // Returns the current native audio renderer of the given audio source.
+    fn audio_renderer_id(&self, participant_id: &str, audio_source: &str) -> Option<u64> {
+        self.inner
+            .renderer_registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, registration)| {
+                matches!(
+                    registration,
+                    RendererRegistration::Audio {
+                        participant_id: p,
+                        audio_source: s,
+                    } if p == participant_id && s == audio_source
+                )
+            })
+            .map(|(renderer_id, _)| *renderer_id)
+            .max()
+    }
+
+    fn maybe_register_completion(&self, completion: Option<PyCallClientCompletion>) -> u64 {
+        let request_id = GLOBAL_CONTEXT.next_request_id();
+
+        if let Some(completion) = completion {
+            self.inner.metrics.request_started(request_id);
+
+            self.inner
+                .completions
+                .lock()
+                .unwrap()
+                .insert(request_id, completion);
+        }
+
+        request_id
+    }
+
+    // Makes a request with a `SyncCompletion` and waits (without holding the
+    // GIL) for its result. Timeouts raise a `TimeoutError`. Signals are checked
+    // while waiting, so the request is cancelled if a signal handler raises an
+    // exception (e.g. `KeyboardInterrupt`).
+    fn request_sync(
+        &self,
+        py: Python<'_>,
+        request: impl FnOnce(PyObject) -> PyResult<u64>,
+    ) -> PyResult<PyObject> {
+        let (completion, mut receiver) = SyncCompletion::create(py)?;
+
+        let request_id = request(completion)?;
+
+        loop {
+            let (waited_receiver, result) = py.allow_threads(move || {
+                let result = receiver.recv_timeout(SYNC_REQUEST_SIGNALS_INTERVAL);
+                (receiver, result)
+            });
+            receiver = waited_receiver;
+
+            match result {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(SyncRequestError::TimedOut)) => {
+                    return Err(exceptions::PyTimeoutError::new_err(
+                        RequestError::TimedOut.message(),
+                    ))
+                }
+                Ok(Err(SyncRequestError::Failed(error))) => {
+                    return Err(exceptions::PyRuntimeError::new_err(error))
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if let Err(error) = py.check_signals() {
+                        complete_request_with_error(
+                            &self.inner,
+                            request_id,
+                            RequestError::Cancelled,
+                        );
+                        return Err(error);
+                    }
+                }
+                // The completion was dropped without being called (e.g. the
+                // client was released).
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(exceptions::PyRuntimeError::new_err(
+                        "request was not completed",
+                    ))
+                }
+            }
+        }
+    }
+
+    fn register_request(
+        &self,
+        completion: Option<PyCallClientCompletion>,
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
+        let timeout = timeout_from_secs(timeout)?;
+
+        let has_completion = completion.is_some();
+
+        let request_id = self.maybe_register_completion(completion);
+
+        if let (true, Some(timeout)) = (has_completion, timeout) {
+            let inner = Arc::downgrade(&self.inner);
+            self.inner.timer.schedule(timeout, move || {
+                if let Some(inner) = inner.upgrade() {
+                    complete_request_with_error(&inner, request_id, RequestError::TimedOut);
+                }
+            });
+        }
+
+        Ok(request_id)
+    }
+}
+
+fn timeout_from_secs(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+    timeout
+        .map(|timeout| {
+            if timeout.is_finite() && timeout >= 0.0 {
+                Ok(Duration::from_secs_f64(timeout))
+            } else {
+                Err(exceptions::PyValueError::new_err(
+                    "timeout should be a non-negative number of seconds",
+                ))
+            }
+        })
+        .transpose()
+}
+
+// Removes the completion of a request that hasn't completed yet and calls it
+// with the given error. Returns whether there was a completion.
+fn complete_request_with_error(
+    inner: &PyCallClientInner,
+    request_id: u64,
+    error: RequestError,
+) -> bool {
+    // Don't lock in the if statement otherwise the lock is held throughout the
+    // callback call.
+    let completion = inner.completions.lock().unwrap().remove(&request_id);
+
+    if let Some(completion) = completion {
+        inner.metrics.request_completed(request_id, false);
+
+        Python::with_gil(|py| completion.complete_with_error(py, error));
+
+        true
+    } else {
+        false
+    }
+}
+
+// Why a blocking request didn't succeed.
+enum SyncRequestError {
+    TimedOut,
+    Failed(String),
+}
+
+type SyncCompletionResult = Result<PyObject, SyncRequestError>;
+
+// A completion callback that forwards the request result to a receiver. This
+// is used by the blocking request variants, which reuse the regular requests
+// and then wait for the result without holding the GIL.
+#[pyclass(module = "daily")]
+pub(crate) struct SyncCompletion {
+    sender: mpsc::Sender<SyncCompletionResult>,
+}
+
+impl SyncCompletion {
+    fn create(py: Python<'_>) -> PyResult<(PyObject, mpsc::Receiver<SyncCompletionResult>)> {
+        let (sender, receiver) = mpsc::channel();
+
+        let completion = Py::new(py, SyncCompletion { sender })?;
+
+        Ok((completion.into_py(py), receiver))
+    }
+
+    // Completes the request with an error that was not reported by the
+    // request itself (e.g. it timed out), keeping timeouts apart from other
+    // errors.
+    pub(crate) fn complete_with_error(&self, error: RequestError) {
+        let error = match error {
+            RequestError::TimedOut => SyncRequestError::TimedOut,
+            error => SyncRequestError::Failed(error.message().to_string()),
+        };
+
+        let _ = self.sender.send(Err(error));
+    }
+}
+
+#[pymethods]
+impl SyncCompletion {
+    #[pyo3(signature = (*args))]
+    fn __call__(&self, args: &Bound<'_, PyTuple>) -> PyResult<()> {
+        // The error is always the last argument and, for binary completions,
+        // the value is the first one.
+        let error = args.get_item(args.len().saturating_sub(1))?;
+
+        let result = if error.is_none() {
+            if args.len() > 1 {
+                Ok(args.get_item(0)?.unbind())
+            } else {
+                Ok(args.py().None())
+            }
         } else {
-            Err(exceptions::PyRuntimeError::new_err(
-                "this object has already been released",
-            ))
-        }
+            Err(SyncRequestError::Failed(error.str()?.to_string()))
+        };
+
+        let _ = self.sender.send(result);
+
+        Ok(())
     }
+}
 
-    fn maybe_register_completion(&self, completion: Option<PyCallClientCompletion>) -> u64 {
-        let request_id = GLOBAL_CONTEXT.next_request_id();
+// Sends the first of the given chunks and the next one once it's been sent,
+// so large transfers don't flood the connection. The completion is called
+// once all the chunks have been sent or as soon as one of them fails.
+fn send_chunks(
+    client: &Bound<'_, PyCallClient>,
+    mut chunks: VecDeque<Value>,
+    participant: Option<String>,
+    completion: Option<PyObject>,
+) -> PyResult<()> {
+    let py = client.py();
+
+    let Some(chunk) = chunks.pop_front() else {
+        return Ok(());
+    };
+
+    let client_ref = client.clone().unbind();
+    let next = Mutex::new(Some((chunks, participant.clone(), completion)));
+
+    let chunk_completion = PyCFunction::new_closure_bound(
+        py,
+        None,
+        None,
+        move |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<()> {
+            let py = args.py();
+            let error = args.get_item(0)?;
+
+            let Some((chunks, participant, completion)) = next.lock().unwrap().take() else {
+                return Ok(());
+            };
+
+            if !error.is_none() || chunks.is_empty() {
+                if let Some(completion) = completion {
+                    completion.call1(py, (error,))?;
+                }
+                return Ok(());
+            }
 
-        if let Some(completion) = completion {
-            self.inner
-                .completions
-                .lock()
-                .unwrap()
-                .insert(request_id, completion);
-        }
+            if let Err(error) =
+                send_chunks(client_ref.bind(py), chunks, participant, completion.clone())
+            {
+                if let Some(completion) = completion {
+                    completion.call1(py, (error.value_bound(py).to_string(),))?;
+                }
+            }
 
-        request_id
-    }
+            Ok(())
+        },
+    )?;
+
+    client.borrow().send_app_message(
+        py,
+        DictValue(chunk).to_object(py),
+        participant.as_deref(),
+        Some(chunk_completion.into_any().unbind()),
+        None,
+        None,
+    )?;
+
+    Ok(())
+}
+
+// Calls the given request method of a call client with a completion for
+// `PyCallClient::request_sync()` and the given timeout as the last arguments,
+// and waits for the result.
+macro_rules! sync_request {
+    ($self:ident, $py:expr, $method:ident($($arg:expr),* $(,)?), $timeout:expr) => {
+        $self.request_sync($py, |completion| {
+            $self.$method($($arg,)* Some(completion), $timeout)
+        })
+    };
 }
 
 #[pymethods]
@@ -86,7 +888,10 @@ impl PyCallClient {
     /// Create a new call client. The new call client can receive meeting events
     /// through an event handler.
     #[new]
-    pub fn new(event_handler: Option<PyObject>) -> PyResult<Self> {
+    #[pyo3(signature = (event_handler = None, weak_event_handler = false))]
+    pub fn new(event_handler: Option<PyObject>, weak_event_handler: bool) -> PyResult<Self> {
+        GLOBAL_CONTEXT.check_initialized()?;
+
         // Make sure the event handler has the right type.
         if let Some(event_handler) = event_handler.clone() {
             let is_event_handler =
@@ -99,6 +904,12 @@ impl PyCallClient {
             }
         }
 
+        let event_handler = Python::with_gil(|py| {
+            event_handler
+                .map(|event_handler| EventHandlerRef::new(py, event_handler, weak_event_handler))
+                .transpose()
+        })?;
+
         let call_client = unsafe { daily_core_call_client_create() };
         if !call_client.is_null() {
             // Get initial values
@@ -110,7 +921,10 @@ impl PyCallClient {
             let subscription_profiles = unsafe { get_subscription_profiles(&mut (*call_client))? };
             let network_stats = unsafe { get_network_stats(&mut (*call_client))? };
 
+            let call_client_id = GLOBAL_CONTEXT.next_call_client_id();
+
             let inner = Arc::new(PyCallClientInner {
+                call_client_id,
                 event_handler_callback: Mutex::new(event_handler),
                 delegates: Mutex::new(PyCallClientDelegateFns {
                     on_event: Some(on_event),
@@ -120,6 +934,27 @@ impl PyCallClient {
                 completions: Mutex::new(HashMap::new()),
                 audio_renderers: Mutex::new(HashMap::new()),
                 video_renderers: Mutex::new(HashMap::new()),
+                video_recorders: Mutex::new(HashMap::new()),
+                audio_level_observers: Mutex::new(HashMap::new()),
+                audio_sample_offsets: Mutex::new(HashMap::new()),
+                av_renderers: Mutex::new(HashMap::new()),
+                av_renderer_ids: Mutex::new(HashMap::new()),
+                meeting_url: Mutex::new(None),
+                call_state: Mutex::new("initialized".to_string()),
+                metrics: CallClientMetrics::default(),
+                network_stats_history: Mutex::new(NetworkStatsHistory::default()),
+                network_quality: Mutex::new(NetworkQualityMonitor::default()),
+                waiters: Mutex::new(HashMap::new()),
+                app_message_rpc: Mutex::new(AppMessageRpc::default()),
+                app_message_chunks: Mutex::new(ChunkAssembler::default()),
+                app_message_topics: Mutex::new(AppMessageTopics::default()),
+                prebuilt_chat_suppressed: Mutex::new(false),
+                transcript: Mutex::new(TranscriptAssembler::default()),
+                recordings: Mutex::new(StreamingTracker::default()),
+                live_streams: Mutex::new(StreamingTracker::default()),
+                reconnector: Mutex::new(Reconnector::default()),
+                renderer_registrations: Mutex::new(HashMap::new()),
+                timer: Timer::default(),
                 // Non-blocking
                 active_speaker: Mutex::new(active_speaker),
                 inputs: Mutex::new(inputs),
@@ -149,13 +984,20 @@ impl PyCallClient {
                 daily_core_call_client_set_delegate(&mut (*call_client), client_delegate);
             }
 
-            Ok(Self {
-                inner,
+            let handle = Arc::new(CallClientHandle {
+                id: call_client_id,
+                pid: process::id(),
                 call_client: Mutex::new(Some(CallClientPtr { ptr: call_client })),
+                releasing: AtomicBool::new(false),
+                inner: inner.clone(),
                 delegate_ctx_ptr: DelegateContextPtr {
                     ptr: delegate_ctx_ptr,
                 },
-            })
+            });
+
+            GLOBAL_CONTEXT.register_call_client(&handle);
+
+            Ok(Self { handle, inner })
         } else {
             Err(exceptions::PyRuntimeError::new_err(
                 "unable to create a CallClient() object",
@@ -170,54 +1012,73 @@ impl PyCallClient {
     /// If this function is not called we will attempt to automatically call it
     /// during garbage collection. However, that's not guaranteed (e.g. if
     /// there's a circular dependency with the registered event handler),
-    /// therefore it is strongly recommended to always call this function.
+    /// therefore it is strongly recommended to always call this function, use
+    /// the call client as a context manager or create it with
+    /// `weak_event_handler=True`.
     pub fn release(&self, py: Python<'_>) -> PyResult<()> {
-        // Hold the call client lock for the whole function so no one else can
-        // grab it while we are releasing.
-        let mut call_client = self.call_client.lock().unwrap();
-
-        // If we have already been released throw an exception.
-        if call_client.is_none() {
-            return Err(exceptions::PyRuntimeError::new_err(
-                "this object has already been released",
-            ));
-        }
-
-        {
-            // Cleanup video/audio delegates so they are not called during
-            // destroy. Do it inside a new scope so the lock gets released.
-            //
-            // Note that we don't cleanup the event delegate because we might be
-            // waiting on completions to finish (e.g. leave).
-            let mut delegates = self.inner.delegates.lock().unwrap();
-            delegates.on_audio_data.take();
-            delegates.on_video_frame.take();
-        }
+        self.handle.release(py)
+    }
 
-        let mut call_client_cpy = call_client.as_ref().unwrap().clone();
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
 
-        // Here we release the GIL so we can allow any event delegates to
-        // finish. The event delegates will be waiting on the GIL and
-        // execute at this point. But since we just cleanup the delegates
-        // above, the events will actually be a no-op.
-        py.allow_threads(move || unsafe {
-            daily_core_call_client_destroy(call_client_cpy.as_ptr());
-        });
+    /// Leaves the meeting (waiting for the leave to complete) and releases the
+    /// call client.
+    #[pyo3(signature = (_exc_type, _exc_value, _traceback))]
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: PyObject,
+        _exc_value: PyObject,
+        _traceback: PyObject,
+    ) -> PyResult<bool> {
+        self.handle.leave_and_release(py, EXIT_LEAVE_TIMEOUT)?;
+        Ok(false)
+    }
 
-        // Remove any reference to the Python's event handler. This should get
-        // rid of any circular dependency.
-        self.inner.event_handler_callback.lock().unwrap().take();
+    fn __aenter__<'py>(slf: Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        let future = slf
+            .py()
+            .import_bound("asyncio")?
+            .call_method0("get_running_loop")?
+            .call_method0("create_future")?;
 
-        // Cleanup the delegate context. The delegate context still has one
-        // reference count (because of we drop it but increase it again every
-        // time a delegate happens). After the client is destroyed it is safe to
-        // simply get rid of it.
-        let _delegate_ctx = unsafe { Arc::from_raw(self.delegate_ctx_ptr.ptr) };
+        future.call_method1("set_result", (slf,))?;
 
-        // Release the call client pointer. We won't need it anymore.
-        *call_client = None;
+        Ok(future)
+    }
 
-        Ok(())
+    /// Same as `__exit__` but leaving and releasing happens in the event loop's
+    /// default executor so the event loop is not blocked.
+    #[pyo3(signature = (_exc_type, _exc_value, _traceback))]
+    fn __aexit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: PyObject,
+        _exc_value: PyObject,
+        _traceback: PyObject,
+    ) -> PyResult<PyObject> {
+        let handle = self.handle.clone();
+
+        let exit = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |args: &Bound<'_, PyTuple>,
+                  _kwargs: Option<&Bound<'_, PyDict>>|
+                  -> PyResult<bool> {
+                handle.leave_and_release(args.py(), EXIT_LEAVE_TIMEOUT)?;
+                Ok(false)
+            },
+        )?;
+
+        let future = py
+            .import_bound("asyncio")?
+            .call_method0("get_running_loop")?
+            .call_method1("run_in_executor", (py.None(), exit))?;
+
+        Ok(future.unbind())
     }
 
     /// Join a meeting given the `meeting_url` and the optional `meeting_token`
@@ -228,14 +1089,19 @@ impl PyCallClient {
     /// :param str meeting_token: Meeting token if needed. This is needed if the client is an owner of the meeting
     /// :param dict client_settings: See :ref:`ClientSettings`
     /// :param func completion: An optional completion callback with two parameters: (:ref:`CallClientJoinData`, :ref:`CallClientError`)
-    #[pyo3(signature = (meeting_url, meeting_token = None, client_settings = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (meeting_url, meeting_token = None, client_settings = None, completion = None, timeout = None))]
     pub fn join(
         &self,
         meeting_url: &str,
         meeting_token: Option<&str>,
         client_settings: Option<PyObject>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -248,20 +1114,35 @@ impl PyCallClient {
             .or(None);
 
         // Client settings
-        let client_settings_cstr = Python::with_gil(|py| {
+        let client_settings_string = Python::with_gil(|py| {
             client_settings
                 .map(|settings| {
                     let settings_map: HashMap<String, DictValue> = settings.extract(py).unwrap();
-                    let settings_string = serde_json::to_string(&settings_map).unwrap();
-                    CString::new(settings_string).expect("invalid client settings string")
+                    serde_json::to_string(&settings_map).unwrap()
                 })
                 .or(None)
         });
+        let client_settings_cstr = client_settings_string.as_ref().map(|settings| {
+            CString::new(settings.as_str()).expect("invalid client settings string")
+        });
 
-        unsafe {
-            let request_id =
-                self.maybe_register_completion(completion.map(PyCallClientCompletion::BinaryFn));
+        *self.inner.meeting_url.lock().unwrap() = Some(meeting_url.to_string());
 
+        // Recordings and live streams from a previous meeting don't apply.
+        self.inner.recordings.lock().unwrap().clear();
+        self.inner.live_streams.lock().unwrap().clear();
+
+        // Keep the join parameters in case we need to rejoin.
+        self.inner.reconnector.lock().unwrap().joining(JoinParams {
+            meeting_url: meeting_url.to_string(),
+            meeting_token: meeting_token.map(str::to_string),
+            client_settings: client_settings_string,
+        });
+
+        let request_id =
+            self.register_request(completion.map(PyCallClientCompletion::BinaryFn), timeout)?;
+
+        unsafe {
             daily_core_call_client_join(
                 call_client.as_mut(),
                 request_id,
@@ -275,25 +1156,105 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`join`, but blocks until the request completes. The GIL is
+    /// released while waiting. A `RuntimeError` is raised if the request fails
+    /// and a `TimeoutError` if it doesn't complete within `timeout`. The
+    /// request is cancelled if a signal handler raises an exception (e.g.
+    /// `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param str meeting_url: The URL of the Daily meeting to join
+    /// :param str meeting_token: Meeting token if needed. This is needed if the client is an owner of the meeting
+    /// :param dict client_settings: See :ref:`ClientSettings`
+    /// :param float timeout: An optional timeout in seconds
+    ///
+    /// :return: See :ref:`CallClientJoinData`
+    /// :rtype: dict
+    #[pyo3(signature = (meeting_url, meeting_token = None, client_settings = None, timeout = None))]
+    pub fn join_sync(
+        &self,
+        py: Python<'_>,
+        meeting_url: &str,
+        meeting_token: Option<&str>,
+        client_settings: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            join(meeting_url, meeting_token, client_settings),
+            timeout
+        )
     }
 
     /// Leave a previously joined meeting.
     ///
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (completion = None))]
-    pub fn leave(&self, completion: Option<PyObject>) -> PyResult<()> {
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (completion = None, timeout = None))]
+    pub fn leave(&self, completion: Option<PyObject>, timeout: Option<f64>) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
+        self.inner.reconnector.lock().unwrap().leaving();
+
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_leave(call_client.as_mut(), request_id);
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`leave`, but blocks until the request completes. The GIL
+    /// is released while waiting. A `RuntimeError` is raised if the request
+    /// fails and a `TimeoutError` if it doesn't complete within `timeout`. The
+    /// request is cancelled if a signal handler raises an exception (e.g.
+    /// `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (timeout = None))]
+    pub fn leave_sync(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<PyObject> {
+        sync_request!(self, py, leave(), timeout)
+    }
+
+    /// Cancels a pending request. The request completion callback is called
+    /// with a cancellation error. Note that the request might still be
+    /// processed, the completion callback just won't wait for it.
+    ///
+    /// :param int request_id: The ID returned by the request function
+    ///
+    /// :return: Whether the request was pending and got cancelled
+    /// :rtype: bool
+    pub fn cancel(&self, request_id: u64) -> PyResult<bool> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        Ok(complete_request_with_error(
+            &self.inner,
+            request_id,
+            RequestError::Cancelled,
+        ))
+    }
+
+    /// Returns the ID of this call client. IDs are unique within the process
+    /// and identify the call client in :func:`Daily.shutdown` failures.
+    ///
+    /// :return: The call client ID
+    /// :rtype: int
+    pub fn id(&self) -> u64 {
+        self.handle.id()
     }
 
     /// Sets this client's user name. The user name is what other participants
@@ -338,17 +1299,9 @@ impl PyCallClient {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
-        unsafe {
-            let participants_ptr = daily_core_call_client_participants(call_client.as_mut());
-            let participants_string = CStr::from_ptr(participants_ptr)
-                .to_string_lossy()
-                .into_owned();
-
-            let participants: HashMap<String, DictValue> =
-                serde_json::from_str(participants_string.as_str()).unwrap();
+        let participants = unsafe { get_participants(call_client.as_mut()) };
 
-            Python::with_gil(|py| Ok(participants.to_object(py)))
-        }
+        Python::with_gil(|py| Ok(DictValue(participants).to_object(py)))
     }
 
     /// Returns the number of hidden and non-hidden participants in the meeting.
@@ -362,16 +1315,121 @@ impl PyCallClient {
         Ok(self.inner.participant_counts.lock().unwrap().clone())
     }
 
+    /// Waits until the call state is the given one. The GIL is released while
+    /// waiting. This must not be called from event handlers or completion
+    /// callbacks.
+    ///
+    /// :param str state: See :ref:`CallState`
+    /// :param float timeout: An optional timeout in seconds. A `TimeoutError` is raised if the state is not reached in time
+    ///
+    /// :return: The call state
+    /// :rtype: str
+    #[pyo3(signature = (state, timeout = None))]
+    pub fn wait_for_call_state(
+        &self,
+        py: Python<'_>,
+        state: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        if !waiter::CALL_STATES.contains(&state) {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid call state '{state}'"
+            )));
+        }
+
+        self.wait_for(py, WaitCondition::CallState(state.to_string()), timeout)
+    }
+
+    /// Waits until a participant is present in the meeting. The participant is
+    /// given either by its ID (or "local") or by a predicate that receives a
+    /// participant and returns whether it's the one we are waiting for. The GIL
+    /// is released while waiting. This must not be called from event handlers
+    /// or completion callbacks.
+    ///
+    /// :param participant: A participant ID or a function that receives a :ref:`Participant` and returns a bool
+    /// :param float timeout: An optional timeout in seconds. A `TimeoutError` is raised if the participant is not present in time
+    ///
+    /// :return: See :ref:`Participant`
+    /// :rtype: dict
+    #[pyo3(signature = (participant, timeout = None))]
+    pub fn wait_for_participant(
+        &self,
+        py: Python<'_>,
+        participant: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        let matcher = if let Ok(participant_id) = participant.extract::<String>(py) {
+            ParticipantMatcher::Id(participant_id)
+        } else if participant.bind(py).is_callable() {
+            ParticipantMatcher::Predicate(participant)
+        } else {
+            return Err(exceptions::PyTypeError::new_err(
+                "participant should be a participant ID or a function",
+            ));
+        };
+
+        self.wait_for(py, WaitCondition::Participant(matcher), timeout)
+    }
+
+    /// Waits until a track of the given participant reaches the given state
+    /// (e.g. until their camera is playable). The GIL is released while
+    /// waiting. This must not be called from event handlers or completion
+    /// callbacks.
+    ///
+    /// :param str participant_id: The ID of the participant (or "local")
+    /// :param str kind: The track kind: "camera", "microphone", "screenVideo" or "screenAudio"
+    /// :param str state: The track state. See :ref:`ParticipantMediaInfo`
+    /// :param float timeout: An optional timeout in seconds. A `TimeoutError` is raised if the track doesn't reach the state in time
+    ///
+    /// :return: See :ref:`ParticipantMediaInfo`
+    /// :rtype: dict
+    #[pyo3(signature = (participant_id, kind = "camera", state = "playable", timeout = None))]
+    pub fn wait_for_track(
+        &self,
+        py: Python<'_>,
+        participant_id: &str,
+        kind: &str,
+        state: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        if !waiter::TRACK_KINDS.contains(&kind) {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid track kind '{kind}'"
+            )));
+        }
+
+        if !waiter::TRACK_STATES.contains(&state) {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid track state '{state}'"
+            )));
+        }
+
+        self.wait_for(
+            py,
+            WaitCondition::Track {
+                participant_id: participant_id.to_string(),
+                kind: kind.to_string(),
+                state: state.to_string(),
+            },
+            timeout,
+        )
+    }
+
     /// Updates remote participants.
     ///
     /// :param dict remote_participants: See :ref:`RemoteParticipantUpdates`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (remote_participants, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (remote_participants, completion = None, timeout = None))]
     pub fn update_remote_participants(
         &self,
         remote_participants: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -384,7 +1442,7 @@ impl PyCallClient {
             CString::new(remote_participants_string).expect("invalid remote participants string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_remote_participants(
@@ -394,19 +1452,49 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_remote_participants`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict remote_participants: See :ref:`RemoteParticipantUpdates`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (remote_participants, timeout = None))]
+    pub fn update_remote_participants_sync(
+        &self,
+        py: Python<'_>,
+        remote_participants: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            update_remote_participants(remote_participants),
+            timeout
+        )
     }
 
     /// Ejects remote participants.
     ///
     /// :param list ids: A list of ids of remote participants to eject
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (ids, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (ids, completion = None, timeout = None))]
     pub fn eject_remote_participants(
         &self,
         ids: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -417,7 +1505,7 @@ impl PyCallClient {
         let ids_cstr = CString::new(ids_string).expect("invalid ids string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_eject_remote_participants(
@@ -427,7 +1515,27 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`eject_remote_participants`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param list ids: A list of ids of remote participants to eject
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (ids, timeout = None))]
+    pub fn eject_remote_participants_sync(
+        &self,
+        py: Python<'_>,
+        ids: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, eject_remote_participants(ids), timeout)
     }
 
     /// Returns the current client inputs. The inputs define the call client
@@ -447,12 +1555,17 @@ impl PyCallClient {
     ///
     /// :param dict input_settings: See :ref:`InputSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (input_settings, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (input_settings, completion = None, timeout = None))]
     pub fn update_inputs(
         &self,
         input_settings: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -465,7 +1578,7 @@ impl PyCallClient {
             CString::new(input_settings_string).expect("invalid input settings string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_inputs(
@@ -475,7 +1588,112 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_inputs`, but blocks until the request completes.
+    /// The GIL is released while waiting. A `RuntimeError` is raised if the
+    /// request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict input_settings: See :ref:`InputSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (input_settings, timeout = None))]
+    pub fn update_inputs_sync(
+        &self,
+        py: Python<'_>,
+        input_settings: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, update_inputs(input_settings), timeout)
+    }
+
+    /// Binds virtual devices to this client. The camera and microphone devices
+    /// are used as this client's inputs (see :func:`update_inputs`), so other
+    /// clients in the same application can use different devices. Whether each
+    /// input is enabled doesn't change.
+    ///
+    /// Speaker devices can't be bound to a client. The audio device module and
+    /// the selected speaker are shared by all the clients in the process, so
+    /// the speaker is selected with :func:`Daily.select_speaker_device` and
+    /// receives the audio of all the clients.
+    ///
+    /// :param str camera: The name of a virtual camera device created with :func:`Daily.create_camera_device`
+    /// :param str microphone: The name of a virtual microphone device created with :func:`Daily.create_microphone_device`
+    /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (camera = None, microphone = None, completion = None, timeout = None))]
+    pub fn set_devices(
+        &self,
+        camera: Option<&str>,
+        microphone: Option<&str>,
+        completion: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let mut input_settings = serde_json::Map::new();
+
+        // Only update the device, so disabled inputs stay disabled.
+        if let Some(camera) = camera {
+            input_settings.insert(
+                "camera".to_string(),
+                serde_json::json!({ "settings": { "deviceId": camera } }),
+            );
+        }
+        if let Some(microphone) = microphone {
+            input_settings.insert(
+                "microphone".to_string(),
+                serde_json::json!({ "settings": { "deviceId": microphone } }),
+            );
+        }
+
+        let input_settings_string = serde_json::to_string(&input_settings).unwrap();
+
+        let input_settings_cstr =
+            CString::new(input_settings_string).expect("invalid input settings string");
+
+        let request_id =
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
+
+        unsafe {
+            daily_core_call_client_update_inputs(
+                call_client.as_mut(),
+                request_id,
+                input_settings_cstr.as_ptr(),
+            );
+        }
+
+        Ok(request_id)
+    }
+
+    /// Same as :func:`set_devices`, but blocks until the request completes. The
+    /// GIL is released while waiting. A `RuntimeError` is raised if the request
+    /// fails and a `TimeoutError` if it doesn't complete within `timeout`. The
+    /// request is cancelled if a signal handler raises an exception (e.g.
+    /// `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param str camera: The name of a virtual camera device created with :func:`Daily.create_camera_device`
+    /// :param str microphone: The name of a virtual microphone device created with :func:`Daily.create_microphone_device`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (camera = None, microphone = None, timeout = None))]
+    pub fn set_devices_sync(
+        &self,
+        py: Python<'_>,
+        camera: Option<&str>,
+        microphone: Option<&str>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, set_devices(camera, microphone), timeout)
     }
 
     /// Returns the current client publishing settings. The publishing settings
@@ -496,12 +1714,17 @@ impl PyCallClient {
     ///
     /// :param dict publishing_settings: See :ref:`PublishingSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (publishing_settings, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (publishing_settings, completion = None, timeout = None))]
     pub fn update_publishing(
         &self,
         publishing_settings: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -514,7 +1737,7 @@ impl PyCallClient {
             CString::new(publishing_settings_string).expect("invalid publishing settings string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_publishing(
@@ -524,7 +1747,27 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_publishing`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict publishing_settings: See :ref:`PublishingSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (publishing_settings, timeout = None))]
+    pub fn update_publishing_sync(
+        &self,
+        py: Python<'_>,
+        publishing_settings: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, update_publishing(publishing_settings), timeout)
     }
 
     /// Returns the current client subscriptions. The client subscriptions is a
@@ -547,13 +1790,18 @@ impl PyCallClient {
     /// :param dict participant_settings: See :ref:`ParticipantSubscriptions`
     /// :param dict profile_settings: See :ref:`SubscriptionProfileSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (participant_settings = None, profile_settings = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (participant_settings = None, profile_settings = None, completion = None, timeout = None))]
     pub fn update_subscriptions(
         &self,
         participant_settings: Option<PyObject>,
         profile_settings: Option<PyObject>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -580,7 +1828,7 @@ impl PyCallClient {
         });
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_subscriptions(
@@ -595,7 +1843,34 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_subscriptions`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict participant_settings: See :ref:`ParticipantSubscriptions`
+    /// :param dict profile_settings: See :ref:`SubscriptionProfileSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (participant_settings = None, profile_settings = None, timeout = None))]
+    pub fn update_subscriptions_sync(
+        &self,
+        py: Python<'_>,
+        participant_settings: Option<PyObject>,
+        profile_settings: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            update_subscriptions(participant_settings, profile_settings),
+            timeout
+        )
     }
 
     /// Returns the current client subscription profiles. A subscription profile
@@ -614,12 +1889,17 @@ impl PyCallClient {
     ///
     /// :param dict profile_settings: See :ref:`SubscriptionProfileSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (profile_settings, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (profile_settings, completion = None, timeout = None))]
     pub fn update_subscription_profiles(
         &self,
         profile_settings: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -631,7 +1911,7 @@ impl PyCallClient {
             CString::new(profile_settings_string).expect("invalid profile settings string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_subscription_profiles(
@@ -641,7 +1921,32 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_subscription_profiles`, but blocks until the
+    /// request completes. The GIL is released while waiting. A `RuntimeError`
+    /// is raised if the request fails and a `TimeoutError` if it doesn't
+    /// complete within `timeout`. The request is cancelled if a signal handler
+    /// raises an exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict profile_settings: See :ref:`SubscriptionProfileSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (profile_settings, timeout = None))]
+    pub fn update_subscription_profiles_sync(
+        &self,
+        py: Python<'_>,
+        profile_settings: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            update_subscription_profiles(profile_settings),
+            timeout
+        )
     }
 
     /// Updates the client permissions. This will only update permissions for
@@ -650,12 +1955,17 @@ impl PyCallClient {
     ///
     /// :param dict permissions: See :ref:`ParticipantPermissions`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (permissions, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (permissions, completion = None, timeout = None))]
     pub fn update_permissions(
         &self,
         permissions: PyObject,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -667,7 +1977,7 @@ impl PyCallClient {
             CString::new(permissions_string).expect("invalid permissions string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_permissions(
@@ -677,7 +1987,55 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_permissions`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict permissions: See :ref:`ParticipantPermissions`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (permissions, timeout = None))]
+    pub fn update_permissions_sync(
+        &self,
+        py: Python<'_>,
+        permissions: PyObject,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, update_permissions(permissions), timeout)
+    }
+
+    /// Returns the state of the recordings seen since the meeting was joined,
+    /// as tracked from the recording events. This doesn't block.
+    ///
+    /// :return: A mapping of stream IDs to :ref:`StreamingState`
+    /// :rtype: dict
+    pub fn recordings(&self, py: Python<'_>) -> PyResult<PyObject> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let recordings = self.inner.recordings.lock().unwrap().to_value();
+
+        Ok(DictValue(recordings).to_object(py))
+    }
+
+    /// Returns the state of the live streams seen since the meeting was
+    /// joined, as tracked from the live stream events. This doesn't block.
+    ///
+    /// :return: A mapping of stream IDs to :ref:`StreamingState`
+    /// :rtype: dict
+    pub fn live_streams(&self, py: Python<'_>) -> PyResult<PyObject> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let live_streams = self.inner.live_streams.lock().unwrap().to_value();
+
+        Ok(DictValue(live_streams).to_object(py))
     }
 
     /// Starts a recording, if recording is enabled for the current room.
@@ -686,14 +2044,19 @@ impl PyCallClient {
     /// :param str stream_id: A unique stream identifier. Multiple recording sessions can be started by specifying a unique ID
     /// :param str force_new: Whether to force a new recording
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (streaming_settings = None, stream_id = None, force_new = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (streaming_settings = None, stream_id = None, force_new = None, completion = None, timeout = None))]
     pub fn start_recording(
         &self,
         streaming_settings: Option<PyObject>,
         stream_id: Option<&str>,
         force_new: Option<bool>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -723,7 +2086,7 @@ impl PyCallClient {
         };
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_start_recording(
@@ -733,7 +2096,36 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`start_recording`, but blocks until the request completes.
+    /// The GIL is released while waiting. A `RuntimeError` is raised if the
+    /// request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict streaming_settings: See :ref:`StreamingSettings`
+    /// :param str stream_id: A unique stream identifier. Multiple recording sessions can be started by specifying a unique ID
+    /// :param str force_new: Whether to force a new recording
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (streaming_settings = None, stream_id = None, force_new = None, timeout = None))]
+    pub fn start_recording_sync(
+        &self,
+        py: Python<'_>,
+        streaming_settings: Option<PyObject>,
+        stream_id: Option<&str>,
+        force_new: Option<bool>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            start_recording(streaming_settings, stream_id, force_new),
+            timeout
+        )
     }
 
     /// Stops an ongoing recording. If multiple recording instances are running,
@@ -742,12 +2134,17 @@ impl PyCallClient {
     ///
     /// :param str stream_id: A unique stream identifier
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (stream_id = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (stream_id = None, completion = None, timeout = None))]
     pub fn stop_recording(
         &self,
         stream_id: Option<&str>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -756,7 +2153,7 @@ impl PyCallClient {
             .or(None);
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_stop_recording(
@@ -768,7 +2165,27 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`stop_recording`, but blocks until the request completes.
+    /// The GIL is released while waiting. A `RuntimeError` is raised if the
+    /// request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param str stream_id: A unique stream identifier
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (stream_id = None, timeout = None))]
+    pub fn stop_recording_sync(
+        &self,
+        py: Python<'_>,
+        stream_id: Option<&str>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, stop_recording(stream_id), timeout)
     }
 
     /// Updates an ongoing recording. If multiple recording instances are
@@ -778,13 +2195,18 @@ impl PyCallClient {
     /// :param dict update_settings: See :ref:`StreamingUpdateSettings`
     /// :param str stream_id: A unique stream identifier
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (update_settings, stream_id = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (update_settings, stream_id = None, completion = None, timeout = None))]
     pub fn update_recording(
         &self,
         update_settings: PyObject,
         stream_id: Option<&str>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -799,7 +2221,7 @@ impl PyCallClient {
             CString::new(update_settings_string).expect("invalid recording settings string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_update_recording(
@@ -812,7 +2234,34 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`update_recording`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict update_settings: See :ref:`StreamingUpdateSettings`
+    /// :param str stream_id: A unique stream identifier
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (update_settings, stream_id = None, timeout = None))]
+    pub fn update_recording_sync(
+        &self,
+        py: Python<'_>,
+        update_settings: PyObject,
+        stream_id: Option<&str>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            update_recording(update_settings, stream_id),
+            timeout
+        )
     }
 
     /// Starts a transcription service. This can be done by meeting owners or
@@ -820,12 +2269,17 @@ impl PyCallClient {
     ///
     /// :param dict settings: See :ref:`TranscriptionSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (settings = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (settings = None, completion = None, timeout = None))]
     pub fn start_transcription(
         &self,
         settings: Option<PyObject>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -839,7 +2293,7 @@ impl PyCallClient {
             .or(None);
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_start_transcription(
@@ -849,7 +2303,27 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`start_transcription`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict settings: See :ref:`TranscriptionSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (settings = None, timeout = None))]
+    pub fn start_transcription_sync(
+        &self,
+        py: Python<'_>,
+        settings: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, start_transcription(settings), timeout)
     }
 
     /// Stops a currently running transcription service. This can be done by
@@ -857,18 +2331,92 @@ impl PyCallClient {
     /// the Daily domain.
     ///
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (completion = None))]
-    pub fn stop_transcription(&self, completion: Option<PyObject>) -> PyResult<()> {
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (completion = None, timeout = None))]
+    pub fn stop_transcription(
+        &self,
+        completion: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_stop_transcription(call_client.as_mut(), request_id);
         }
 
+        Ok(request_id)
+    }
+
+    /// Same as :func:`stop_transcription`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (timeout = None))]
+    pub fn stop_transcription_sync(
+        &self,
+        py: Python<'_>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, stop_transcription(), timeout)
+    }
+
+    /// Returns the transcript assembled from the transcription messages
+    /// received so far (see :func:`EventHandler.on_transcription_message`).
+    /// Transcription messages are grouped into utterances per participant.
+    /// Interim results are replaced by the final results, which requires
+    /// `includeRawResponse` in the :ref:`TranscriptionSettings`. The transcript
+    /// is kept until :func:`clear_transcript` is called.
+    ///
+    /// :return: A list of :ref:`TranscriptUtterance`, sorted by start time
+    /// :rtype: list
+    pub fn transcript(&self, py: Python<'_>) -> PyResult<PyObject> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let transcript = self.inner.transcript.lock().unwrap().to_value();
+
+        Ok(DictValue(transcript).to_object(py))
+    }
+
+    /// Exports the transcript (see :func:`transcript`) as SubRip (SRT),
+    /// WebVTT or JSON. Timestamps are relative to the start of the first
+    /// utterance.
+    ///
+    /// :param str format: One of "srt", "vtt" or "json"
+    ///
+    /// :return: The exported transcript
+    /// :rtype: str
+    #[pyo3(signature = (format = "json"))]
+    pub fn export_transcript(&self, format: &str) -> PyResult<String> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let format = TranscriptFormat::parse(format).ok_or_else(|| {
+            exceptions::PyValueError::new_err(format!("invalid transcript format '{format}'"))
+        })?;
+
+        Ok(self.inner.transcript.lock().unwrap().export(format))
+    }
+
+    /// Discards the transcript assembled so far.
+    pub fn clear_transcript(&self) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        self.inner.transcript.lock().unwrap().clear();
+
         Ok(())
     }
 
@@ -877,13 +2425,18 @@ impl PyCallClient {
     ///
     /// :param dict settings: See :ref:`DialoutSettings`
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (settings = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (settings = None, completion = None, timeout = None))]
     pub fn start_dialout(
         &self,
         py: Python<'_>,
         settings: Option<PyObject>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -896,7 +2449,7 @@ impl PyCallClient {
             .or(None);
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_start_dialout(
@@ -906,7 +2459,27 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`start_dialout`, but blocks until the request completes.
+    /// The GIL is released while waiting. A `RuntimeError` is raised if the
+    /// request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param dict settings: See :ref:`DialoutSettings`
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (settings = None, timeout = None))]
+    pub fn start_dialout_sync(
+        &self,
+        py: Python<'_>,
+        settings: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, start_dialout(py, settings), timeout)
     }
 
     /// Stops a currently running dial-out service. This can be done by meeting
@@ -914,15 +2487,24 @@ impl PyCallClient {
     ///
     /// :param str participant: The participant of the dial-out session to stop
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (participant, completion = None))]
-    pub fn stop_dialout(&self, participant: &str, completion: Option<PyObject>) -> PyResult<()> {
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (participant, completion = None, timeout = None))]
+    pub fn stop_dialout(
+        &self,
+        participant: &str,
+        completion: Option<PyObject>,
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
         let participant_cstr = CString::new(participant).expect("invalid participant string");
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_stop_dialout(
@@ -932,23 +2514,56 @@ impl PyCallClient {
             );
         }
 
-        Ok(())
+        Ok(request_id)
+    }
+
+    /// Same as :func:`stop_dialout`, but blocks until the request completes.
+    /// The GIL is released while waiting. A `RuntimeError` is raised if the
+    /// request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param str participant: The participant of the dial-out session to stop
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (participant, timeout = None))]
+    pub fn stop_dialout_sync(
+        &self,
+        py: Python<'_>,
+        participant: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(self, py, stop_dialout(participant), timeout)
     }
 
     /// Sends a message to other participants, or another specific participant,
-    /// during the call.
+    /// during the call. Binary values (`bytes`, `bytearray` or `memoryview`)
+    /// can be used anywhere in the message, see :ref:`AppMessageBinary`.
+    ///
+    /// If a topic is given, the message is only delivered to the receivers'
+    /// topic subscribers (see :func:`subscribe_app_messages`) instead of
+    /// :func:`EventHandler.on_app_message`, unless there are none. Other
+    /// clients can follow the :ref:`AppMessageTopic` format.
     ///
     /// :param any message: The message to send (should be serializable to JSON)
     /// :param str participant: The participant to send the message to. Or `None` to broadcast the message
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (message, participant = None , completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    /// :param str topic: An optional topic to send the message to
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (message, participant = None , completion = None, timeout = None, topic = None))]
     pub fn send_app_message(
         &self,
         py: Python<'_>,
         message: PyObject,
         participant: Option<&str>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+        topic: Option<&str>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -964,7 +2579,10 @@ impl PyCallClient {
             })?;
         }
 
-        let message_value: DictValue = message.extract(py).unwrap();
+        let mut message_value = DictValue::extract_with_binary(message.bind(py))?;
+        if let Some(topic) = topic {
+            message_value = DictValue(app_message_topics::wrap_message(topic, message_value.0));
+        }
         let message_string = serde_json::to_string(&message_value.0).unwrap();
         let message_cstr = CString::new(message_string).expect("invalid message string");
 
@@ -972,21 +2590,292 @@ impl PyCallClient {
             .map(|p| CString::new(p).expect("invalid participant string"))
             .or(None);
 
-        let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+        let request_id =
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
+
+        unsafe {
+            daily_core_call_client_send_app_message(
+                call_client.as_mut(),
+                request_id,
+                message_cstr.as_ptr(),
+                participant_cstr
+                    .as_ref()
+                    .map_or(ptr::null(), |s| s.as_ptr()),
+            );
+        }
+
+        Ok(request_id)
+    }
+
+    /// Same as :func:`send_app_message`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param any message: The message to send (should be serializable to JSON)
+    /// :param str participant: The participant to send the message to. Or `None` to broadcast the message
+    /// :param str topic: An optional topic to send the message to
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (message, participant = None, timeout = None, topic = None))]
+    pub fn send_app_message_sync(
+        &self,
+        py: Python<'_>,
+        message: PyObject,
+        participant: Option<&str>,
+        timeout: Option<f64>,
+        topic: Option<&str>,
+    ) -> PyResult<PyObject> {
+        self.request_sync(py, |completion| {
+            self.send_app_message(py, message, participant, Some(completion), timeout, topic)
+        })
+    }
+
+    /// Sends a message that might exceed the app message size limit (e.g. a
+    /// multi-megabyte JSON document). The message is split into chunks that
+    /// are reassembled (and checked for integrity) by the receiving
+    /// daily-python clients, where it's delivered to
+    /// :func:`EventHandler.on_app_message` as a single message. Each chunk is
+    /// sent once the previous one has been sent. Receivers discard incomplete
+    /// transfers if they don't get any of their chunks for 30 seconds. Other
+    /// clients can follow the :ref:`AppMessageChunk` format.
+    ///
+    /// :param any message: The message to send (should be serializable to JSON, up to 64 MB once serialized)
+    /// :param str participant: The participant to send the message to. Or `None` to broadcast the message
+    /// :param int chunk_size: The maximum number of bytes of each app message, including the chunk header. It should be at least 1024
+    /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`). It's called once all the chunks have been sent, or when the first one fails
+    /// :param str topic: An optional topic to send the message to, see :func:`send_app_message`
+    ///
+    /// :return: The transfer ID
+    /// :rtype: str
+    #[pyo3(signature = (message, participant = None, chunk_size = 3000, completion = None, topic = None))]
+    pub fn send_large_app_message(
+        slf: Bound<'_, Self>,
+        message: PyObject,
+        participant: Option<&str>,
+        chunk_size: usize,
+        completion: Option<PyObject>,
+        topic: Option<&str>,
+    ) -> PyResult<String> {
+        let py = slf.py();
+
+        if message.is_none(py) {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid app message '{message}'"
+            )));
+        }
+
+        if chunk_size < app_message_chunks::MIN_CHUNK_SIZE {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "chunk_size should be at least {}",
+                app_message_chunks::MIN_CHUNK_SIZE
+            )));
+        }
+
+        let mut message_value = DictValue::extract_with_binary(message.bind(py))?;
+        if let Some(topic) = topic {
+            message_value = DictValue(app_message_topics::wrap_message(topic, message_value.0));
+        }
+        let message_string = serde_json::to_string(&message_value.0).unwrap();
+
+        if message_string.len() > app_message_chunks::MAX_TRANSFER_SIZE {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "app message is too large ({} bytes, the maximum is {})",
+                message_string.len(),
+                app_message_chunks::MAX_TRANSFER_SIZE
+            )));
+        }
+
+        let (transfer_id, chunks) = app_message_chunks::split_message(&message_string, chunk_size);
+
+        send_chunks(
+            &slf,
+            chunks.into(),
+            participant.map(str::to_string),
+            completion,
+        )?;
+
+        Ok(transfer_id)
+    }
+
+    /// Sends a request to another participant as an app message and waits for
+    /// its response. A correlation ID is attached to the request so the
+    /// response can be matched. The other participant can reply using
+    /// :func:`register_app_message_handler` or, from other clients, following
+    /// the :ref:`AppMessageRpc` format. The GIL is released while waiting. This
+    /// must not be called from event handlers or completion callbacks.
+    ///
+    /// A `RuntimeError` is raised as soon as the request fails to be sent or
+    /// the other participant replies with an error.
+    ///
+    /// :param any message: The request data (should be serializable to JSON)
+    /// :param str participant: The participant to send the request to
+    /// :param str method: An optional method name, used to select the handler on the other side
+    /// :param float timeout: Seconds to wait for the response. A `TimeoutError` is raised if there's no response in time (e.g. the other participant has no handler for the method)
+    ///
+    /// :return: The response data
+    #[pyo3(signature = (message, participant, method = None, timeout = 10.0))]
+    pub fn request_app_message(
+        &self,
+        py: Python<'_>,
+        message: PyObject,
+        participant: &str,
+        method: Option<&str>,
+        timeout: f64,
+    ) -> PyResult<PyObject> {
+        let timeout = timeout_from_secs(Some(timeout))?.unwrap_or_default();
+
+        if message.is_none(py) {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid app message '{message}'"
+            )));
+        }
+
+        let message_value = DictValue::extract_with_binary(message.bind(py))?;
+
+        let id = Uuid::new_v4().to_string();
+
+        let request = RpcMessage::Request {
+            id: id.clone(),
+            method: method.map(str::to_string),
+            data: message_value.0,
+        };
+
+        let (sender, receiver) = mpsc::channel::<RpcResult>();
+
+        // Don't wait for a response if the request can't be sent.
+        let send_error_sender = sender.clone();
+        let completion = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<()> {
+                let error = args.get_item(0)?;
+
+                if !error.is_none() {
+                    let _ = send_error_sender.send(Err(format!(
+                        "unable to send app message request: {}",
+                        error.str()?
+                    )));
+                }
+
+                Ok(())
+            },
+        )?;
+
+        self.inner
+            .app_message_rpc
+            .lock()
+            .unwrap()
+            .add_pending(&id, sender);
+
+        let result = self
+            .send_app_message(
+                py,
+                DictValue(request.to_value()).to_object(py),
+                Some(participant),
+                Some(completion.into_any().unbind()),
+                None,
+                None,
+            )
+            .map(|_| py.allow_threads(move || receiver.recv_timeout(timeout)));
+
+        self.inner
+            .app_message_rpc
+            .lock()
+            .unwrap()
+            .remove_pending(&id);
+
+        match result? {
+            Ok(Ok(data)) => Ok(DictValue(data).to_object_with_binary(py)),
+            Ok(Err(error)) => Err(exceptions::PyRuntimeError::new_err(error)),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(exceptions::PyTimeoutError::new_err(
+                "no app message response received in time",
+            )),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(exceptions::PyRuntimeError::new_err(
+                "this object was released while waiting",
+            )),
+        }
+    }
+
+    /// Registers a handler for app message requests (see
+    /// :func:`request_app_message`) with the given method. The handler is
+    /// called with the request data and the sender's participant ID, and its
+    /// return value (which should be serializable to JSON) is sent back to the
+    /// sender as the response. If the handler raises an exception, the
+    /// exception message is sent back as an error. Requests with no handler
+    /// are not answered (the requester times out), so other clients don't
+    /// race with the one that handles them.
+    ///
+    /// The handler is called from the event thread while holding the GIL, so
+    /// no other events are delivered until it returns. It should return
+    /// quickly and must not call blocking functions (e.g.
+    /// :func:`request_app_message`).
+    ///
+    /// :param str method: The method name
+    /// :param func handler: A function with two parameters: (data, participant ID). Or `None` to unregister the current handler
+    #[pyo3(signature = (method, handler))]
+    pub fn register_app_message_handler(
+        &self,
+        method: &str,
+        handler: Option<PyObject>,
+    ) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        self.inner
+            .app_message_rpc
+            .lock()
+            .unwrap()
+            .set_handler(method, handler);
+
+        Ok(())
+    }
+
+    /// Subscribes to app messages sent to the given topic (see
+    /// :func:`send_app_message`). The callback is called with the message and
+    /// the sender's participant ID. Messages sent to a topic are only delivered
+    /// to its subscribers (in the order they subscribed). If the topic has no
+    /// subscribers, they are delivered to :func:`EventHandler.on_app_message`
+    /// as they were received (see :ref:`AppMessageTopic`).
+    ///
+    /// :param str topic: The topic to subscribe to
+    /// :param func callback: A function with two parameters: (message, participant ID)
+    ///
+    /// :return: The subscription ID, which can be used with :func:`unsubscribe_app_messages`
+    /// :rtype: int
+    #[pyo3(signature = (topic, callback))]
+    pub fn subscribe_app_messages(&self, topic: &str, callback: PyObject) -> PyResult<u64> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        Ok(self
+            .inner
+            .app_message_topics
+            .lock()
+            .unwrap()
+            .subscribe(topic, callback))
+    }
 
-        unsafe {
-            daily_core_call_client_send_app_message(
-                call_client.as_mut(),
-                request_id,
-                message_cstr.as_ptr(),
-                participant_cstr
-                    .as_ref()
-                    .map_or(ptr::null(), |s| s.as_ptr()),
-            );
-        }
+    /// Removes a subscription created with :func:`subscribe_app_messages`.
+    ///
+    /// :param int subscription_id: The subscription ID
+    ///
+    /// :return: Whether the subscription existed
+    /// :rtype: bool
+    #[pyo3(signature = (subscription_id))]
+    pub fn unsubscribe_app_messages(&self, subscription_id: u64) -> PyResult<bool> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
 
-        Ok(())
+        Ok(self
+            .inner
+            .app_message_topics
+            .lock()
+            .unwrap()
+            .unsubscribe(subscription_id))
     }
 
     /// Sends a chat message to Daily's Prebuilt main room.
@@ -994,13 +2883,18 @@ impl PyCallClient {
     /// :param str message: The chat message to send
     /// :param str user_name: The user name that will appear as a sender of the message
     /// :param func completion: An optional completion callback with one parameter: (:ref:`CallClientError`)
-    #[pyo3(signature = (message, user_name = None, completion = None))]
+    /// :param float timeout: An optional timeout in seconds. If the request doesn't complete in time the completion callback is called with a timeout error
+    ///
+    /// :return: The request ID, which can be used with :func:`cancel`
+    /// :rtype: int
+    #[pyo3(signature = (message, user_name = None, completion = None, timeout = None))]
     pub fn send_prebuilt_chat_message(
         &self,
         message: &str,
         user_name: Option<&str>,
         completion: Option<PyObject>,
-    ) -> PyResult<()> {
+        timeout: Option<f64>,
+    ) -> PyResult<u64> {
         // If we have already been released throw an exception.
         let mut call_client = self.check_released()?;
 
@@ -1011,7 +2905,7 @@ impl PyCallClient {
             .or(None);
 
         let request_id =
-            self.maybe_register_completion(completion.map(PyCallClientCompletion::UnaryFn));
+            self.register_request(completion.map(PyCallClientCompletion::UnaryFn), timeout)?;
 
         unsafe {
             daily_core_call_client_send_prebuilt_chat_message(
@@ -1023,6 +2917,49 @@ impl PyCallClient {
             );
         }
 
+        Ok(request_id)
+    }
+
+    /// Same as :func:`send_prebuilt_chat_message`, but blocks until the request
+    /// completes. The GIL is released while waiting. A `RuntimeError` is raised
+    /// if the request fails and a `TimeoutError` if it doesn't complete within
+    /// `timeout`. The request is cancelled if a signal handler raises an
+    /// exception (e.g. `KeyboardInterrupt`).
+    ///
+    /// This must not be called from event handlers or completion callbacks.
+    ///
+    /// :param str message: The chat message to send
+    /// :param str user_name: The user name that will appear as a sender of the message
+    /// :param float timeout: An optional timeout in seconds
+    #[pyo3(signature = (message, user_name = None, timeout = None))]
+    pub fn send_prebuilt_chat_message_sync(
+        &self,
+        py: Python<'_>,
+        message: &str,
+        user_name: Option<&str>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyObject> {
+        sync_request!(
+            self,
+            py,
+            send_prebuilt_chat_message(message, user_name),
+            timeout
+        )
+    }
+
+    /// Incoming Daily Prebuilt chat messages are delivered to
+    /// :func:`EventHandler.on_prebuilt_chat_message` and, by default, also to
+    /// :func:`EventHandler.on_app_message`. This allows not delivering them to
+    /// :func:`EventHandler.on_app_message`.
+    ///
+    /// :param bool suppress: Whether Prebuilt chat messages should not be delivered as app messages
+    #[pyo3(signature = (suppress = true))]
+    pub fn suppress_prebuilt_chat_app_messages(&self, suppress: bool) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        *self.inner.prebuilt_chat_suppressed.lock().unwrap() = suppress;
+
         Ok(())
     }
 
@@ -1037,6 +2974,125 @@ impl PyCallClient {
         Ok(self.inner.network_stats.lock().unwrap().clone())
     }
 
+    /// Returns the network statistics received during the last `window_secs`
+    /// seconds, together with their minimum, maximum and average values. A
+    /// bounded history of the network statistics is kept for each call
+    /// client, even after leaving the meeting.
+    ///
+    /// :param float window_secs: The time window in seconds
+    ///
+    /// :return: See :ref:`NetworkStatsHistory`
+    /// :rtype: dict
+    #[pyo3(signature = (window_secs = 60.0))]
+    pub fn network_stats_history(&self, py: Python<'_>, window_secs: f64) -> PyResult<PyObject> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        if !window_secs.is_finite() || window_secs < 0.0 {
+            return Err(exceptions::PyValueError::new_err(
+                "window_secs should be a non-negative number of seconds",
+            ));
+        }
+
+        Ok(self
+            .inner
+            .network_stats_history
+            .lock()
+            .unwrap()
+            .to_object(py, Duration::from_secs_f64(window_secs)))
+    }
+
+    /// Returns the current network quality of this client, as evaluated with
+    /// the thresholds set with :func:`set_network_quality_thresholds`.
+    ///
+    /// :return: See :ref:`NetworkQuality`
+    /// :rtype: str
+    pub fn network_quality(&self) -> PyResult<&'static str> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        Ok(self
+            .inner
+            .network_quality
+            .lock()
+            .unwrap()
+            .quality()
+            .as_str())
+    }
+
+    /// Sets the thresholds used to evaluate the network quality of this
+    /// client. Thresholds that are not given keep their default values. The
+    /// network quality is evaluated every time network statistics are
+    /// received, and :func:`EventHandler.on_network_quality_changed` is called
+    /// when it changes. Once a quality is entered, a value needs to go past
+    /// the exit threshold to leave it, so values sitting on a threshold don't
+    /// change the quality back and forth.
+    ///
+    /// :param dict thresholds: See :ref:`NetworkQualityThresholds`
+    pub fn set_network_quality_thresholds(&self, thresholds: PyObject) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let thresholds_map: serde_json::Map<String, Value> = Python::with_gil(|py| {
+            let thresholds: HashMap<String, DictValue> = thresholds.extract(py)?;
+            Ok::<_, PyErr>(
+                thresholds
+                    .into_iter()
+                    .map(|(key, value)| (key, value.0))
+                    .collect(),
+            )
+        })?;
+
+        let thresholds = NetworkQualityThresholds::from_value(&thresholds_map)
+            .map_err(exceptions::PyValueError::new_err)?;
+
+        self.inner
+            .network_quality
+            .lock()
+            .unwrap()
+            .set_thresholds(thresholds);
+
+        Ok(())
+    }
+
+    /// Sets the policy used to automatically rejoin the meeting if this client
+    /// gets disconnected (e.g. because of a network issue). The meeting is
+    /// rejoined with the same meeting URL, token and client settings used in
+    /// the last :func:`join` and audio and video renderers are restored. Events
+    /// :func:`EventHandler.on_reconnecting`,
+    /// :func:`EventHandler.on_reconnected` and
+    /// :func:`EventHandler.on_reconnect_failed` are emitted. Reconnection is
+    /// disabled by default. Once enabled, only disconnections caused by errors
+    /// are retried unless other reasons are listed in the policy (e.g. the
+    /// client is not reconnected after being ejected).
+    ///
+    /// :param dict policy: See :ref:`ReconnectPolicy`. Values that are not given keep their default values. If None, reconnection is disabled
+    #[pyo3(signature = (policy))]
+    pub fn set_reconnect_policy(&self, policy: Option<PyObject>) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        self.check_released()?;
+
+        let policy = policy
+            .map(|policy| {
+                let policy_map: serde_json::Map<String, Value> = Python::with_gil(|py| {
+                    let policy: HashMap<String, DictValue> = policy.extract(py)?;
+                    Ok::<_, PyErr>(
+                        policy
+                            .into_iter()
+                            .map(|(key, value)| (key, value.0))
+                            .collect(),
+                    )
+                })?;
+
+                ReconnectPolicy::from_value(&policy_map).map_err(exceptions::PyValueError::new_err)
+            })
+            .transpose()?;
+
+        self.inner.reconnector.lock().unwrap().set_policy(policy);
+
+        Ok(())
+    }
+
     /// Registers an audio renderer for the given audio source of the provided
     /// participant.
     ///
@@ -1076,6 +3132,127 @@ impl PyCallClient {
             );
         }
 
+        self.register_renderer(
+            request_id,
+            RendererRegistration::Audio {
+                participant_id: participant_id.to_string(),
+                audio_source: audio_source.to_string(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Registers an audio level observer for the given audio source of the
+    /// provided participant. Audio levels are computed natively from the
+    /// received audio and the callback is only called every `interval_ms`
+    /// milliseconds with the levels of the audio received during that
+    /// interval. There's one observer per audio source, so this replaces any
+    /// previous observer of the same audio source. Audio renderers of the same
+    /// audio source keep receiving audio.
+    ///
+    /// :param str participant_id: The ID of the participant to observe
+    /// :param function callback: A callback to be called with the audio levels. It receives two arguments: the participant ID and an :ref:`AudioLevel`. If None, the current observer is removed
+    /// :param int interval_ms: How often (in milliseconds) the callback should be called
+    /// :param str audio_source: The audio source of the remote participant to observe (e.g. `microphone`, `screenAudio` or a custom track name)
+    #[pyo3(signature = (participant_id, callback, interval_ms = 100, audio_source = "microphone"))]
+    pub fn set_audio_level_observer(
+        &self,
+        participant_id: &str,
+        callback: Option<PyObject>,
+        interval_ms: u64,
+        audio_source: &str,
+    ) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let participant_cstr = CString::new(participant_id).expect("invalid participant ID string");
+        let audio_source_cstr = CString::new(audio_source).expect("invalid audio source string");
+
+        self.inner
+            .audio_level_observers
+            .lock()
+            .unwrap()
+            .retain(|_, observer| {
+                observer.participant_id != participant_id || observer.audio_source != audio_source
+            });
+
+        let renderer_id = self.audio_renderer_id(participant_id, audio_source);
+
+        let Some(callback) = callback else {
+            // Native renderers can't be removed, but we don't need to restore
+            // one that was only used for audio levels after reconnecting.
+            if let Some(renderer_id) = renderer_id {
+                let has_renderer = self
+                    .inner
+                    .audio_renderers
+                    .lock()
+                    .unwrap()
+                    .contains_key(&renderer_id)
+                    || self
+                        .inner
+                        .av_renderer_ids
+                        .lock()
+                        .unwrap()
+                        .contains_key(&renderer_id);
+
+                if !has_renderer {
+                    self.inner
+                        .renderer_registrations
+                        .lock()
+                        .unwrap()
+                        .remove(&renderer_id);
+                }
+            }
+            return Ok(());
+        };
+
+        let observer = AudioLevelObserver::new(
+            callback,
+            Duration::from_millis(interval_ms),
+            participant_id,
+            audio_source,
+        );
+
+        // Share the native audio renderer of this audio source, if there's
+        // one. Otherwise setting a new one would replace it.
+        if let Some(renderer_id) = renderer_id {
+            self.inner
+                .audio_level_observers
+                .lock()
+                .unwrap()
+                .insert(renderer_id, observer);
+            return Ok(());
+        }
+
+        let request_id = self.maybe_register_completion(None);
+
+        // Use the request_id as our renderer_id (it will be unique anyways) and
+        // register the audio level observer.
+        self.inner
+            .audio_level_observers
+            .lock()
+            .unwrap()
+            .insert(request_id, observer);
+
+        unsafe {
+            daily_core_call_client_set_participant_audio_renderer(
+                call_client.as_mut(),
+                request_id,
+                request_id,
+                participant_cstr.as_ptr(),
+                audio_source_cstr.as_ptr(),
+            );
+        }
+
+        self.register_renderer(
+            request_id,
+            RendererRegistration::Audio {
+                participant_id: participant_id.to_string(),
+                audio_source: audio_source.to_string(),
+            },
+        );
+
         Ok(())
     }
 
@@ -1128,8 +3305,260 @@ impl PyCallClient {
             );
         }
 
+        self.register_renderer(
+            request_id,
+            RendererRegistration::Video {
+                participant_id: participant_id.to_string(),
+                video_source: video_source.to_string(),
+                color_format: color_format.to_string(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Registers an A/V renderer for the given audio and video sources of the
+    /// provided participant. The callback is called on every received video
+    /// frame together with all the audio received since the previous video
+    /// frame. If no video frames are received (e.g. the participant's camera is
+    /// off), the callback is still called with the pending audio and no video
+    /// frame.
+    ///
+    /// Audio and video are grouped in the order they are received, timestamps
+    /// are not used. Note that :attr:`AudioData.timestamp_us` and
+    /// :attr:`VideoFrame.timestamp_us` come from different clocks and can't
+    /// be compared.
+    ///
+    /// :param str participant_id: The ID of the participant to receive audio and video from
+    /// :param function callback: A callback to be called when audio or video are available. It receives three arguments: the participant ID, a :class:`VideoFrame` (or None) and an :class:`AudioData` (or None)
+    /// :param str audio_source: The audio source of the remote participant to receive (e.g. `microphone`, `screenAudio` or a custom track name)
+    /// :param str video_source: The video source of the remote participant to receive (e.g. `camera`, `screenVideo` or a custom track name)
+    /// :param str color_format: The color format that frames should be received. See :ref:`ColorFormat`
+    #[pyo3(signature = (participant_id, callback, audio_source = "microphone", video_source = "camera", color_format = "RGBA"))]
+    pub fn set_av_renderer(
+        &self,
+        participant_id: &str,
+        callback: PyObject,
+        audio_source: &str,
+        video_source: &str,
+        color_format: &str,
+    ) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let participant_cstr = CString::new(participant_id).expect("invalid participant ID string");
+        let audio_source_cstr = CString::new(audio_source).expect("invalid audio source string");
+        let video_source_cstr = CString::new(video_source).expect("invalid video source string");
+        let color_format_cstr = CString::new(color_format).expect("invalid color format string");
+
+        if ColorFormat::from_str(color_format).is_err() {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "invalid color format '{color_format}'"
+            )));
+        }
+
+        let audio_renderer_id = self.maybe_register_completion(None);
+        let video_renderer_id = self.maybe_register_completion(None);
+
+        // The audio renderer ID is also used as the A/V renderer ID.
+        self.inner
+            .av_renderers
+            .lock()
+            .unwrap()
+            .insert(audio_renderer_id, AvRenderer::new(callback));
+
+        {
+            let mut av_renderer_ids = self.inner.av_renderer_ids.lock().unwrap();
+            av_renderer_ids.insert(audio_renderer_id, audio_renderer_id);
+            av_renderer_ids.insert(video_renderer_id, audio_renderer_id);
+        }
+
+        unsafe {
+            daily_core_call_client_set_participant_audio_renderer(
+                call_client.as_mut(),
+                audio_renderer_id,
+                audio_renderer_id,
+                participant_cstr.as_ptr(),
+                audio_source_cstr.as_ptr(),
+            );
+
+            daily_core_call_client_set_participant_video_renderer(
+                call_client.as_mut(),
+                video_renderer_id,
+                video_renderer_id,
+                participant_cstr.as_ptr(),
+                video_source_cstr.as_ptr(),
+                color_format_cstr.as_ptr(),
+            );
+        }
+
+        self.register_renderer(
+            audio_renderer_id,
+            RendererRegistration::Audio {
+                participant_id: participant_id.to_string(),
+                audio_source: audio_source.to_string(),
+            },
+        );
+
+        self.register_renderer(
+            video_renderer_id,
+            RendererRegistration::Video {
+                participant_id: participant_id.to_string(),
+                video_source: video_source.to_string(),
+                color_format: color_format.to_string(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Starts a local recording of the given video source of the provided
+    /// participant. Video frames are received in I420 and written to a Y4M
+    /// file as they arrive, without calling into Python. The timestamp of each
+    /// written frame is stored in a `<path>.timestamps` sidecar file.
+    ///
+    /// The recording replaces any video renderer of the same video source,
+    /// which is set again when the recording stops. Skipped frames (e.g. after
+    /// a resolution change) and write errors are reported with
+    /// :func:`daily.EventHandler.on_local_video_recording_error`.
+    ///
+    /// :param str participant_id: The ID of the participant to record video from
+    /// :param str path: The path of the Y4M file to write
+    /// :param str video_source: The video source of the remote participant to record (e.g. `camera`, `screenVideo` or a custom track name)
+    #[pyo3(signature = (participant_id, path, video_source = "camera"))]
+    pub fn start_local_video_recording(
+        &self,
+        participant_id: &str,
+        path: &str,
+        video_source: &str,
+    ) -> PyResult<()> {
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let participant_cstr = CString::new(participant_id).expect("invalid participant ID string");
+        let video_source_cstr = CString::new(video_source).expect("invalid video source string");
+        let color_format_cstr = CString::new("I420").expect("invalid color format string");
+
+        let mut recorders = self.inner.video_recorders.lock().unwrap();
+
+        if recorders.values().any(|recorder| {
+            recorder.participant_id() == participant_id && recorder.video_source() == video_source
+        }) {
+            return Err(exceptions::PyRuntimeError::new_err(format!(
+                "'{video_source}' of participant '{participant_id}' is already being recorded"
+            )));
+        }
+
+        let recorder = VideoRecorder::new(participant_id, video_source, path).map_err(|error| {
+            exceptions::PyIOError::new_err(format!(
+                "unable to create local video recording '{path}': {error}"
+            ))
+        })?;
+
+        let request_id = self.maybe_register_completion(None);
+
+        // Use the request_id as our renderer_id (it will be unique anyways) and
+        // register the recorder.
+        recorders.insert(request_id, recorder);
+        drop(recorders);
+
+        unsafe {
+            daily_core_call_client_set_participant_video_renderer(
+                call_client.as_mut(),
+                request_id,
+                request_id,
+                participant_cstr.as_ptr(),
+                video_source_cstr.as_ptr(),
+                color_format_cstr.as_ptr(),
+            );
+        }
+
+        self.register_renderer(
+            request_id,
+            RendererRegistration::Video {
+                participant_id: participant_id.to_string(),
+                video_source: video_source.to_string(),
+                color_format: "I420".to_string(),
+            },
+        );
+
         Ok(())
     }
+
+    /// Stops a local recording previously started with
+    /// :func:`start_local_video_recording`.
+    ///
+    /// :param str participant_id: The ID of the participant being recorded
+    /// :param str video_source: The video source of the remote participant being recorded
+    ///
+    /// :return: The number of frames written to the recording
+    /// :rtype: int
+    #[pyo3(signature = (participant_id, video_source = "camera"))]
+    pub fn stop_local_video_recording(
+        &self,
+        py: Python<'_>,
+        participant_id: &str,
+        video_source: &str,
+    ) -> PyResult<u64> {
+        // If we have already been released throw an exception.
+        let mut call_client = self.check_released()?;
+
+        let recorder = {
+            let mut recorders = self.inner.video_recorders.lock().unwrap();
+
+            let renderer_id = recorders
+                .iter()
+                .find(|(_, recorder)| {
+                    recorder.participant_id() == participant_id
+                        && recorder.video_source() == video_source
+                })
+                .map(|(renderer_id, _)| *renderer_id);
+
+            renderer_id.and_then(|id| recorders.remove(&id).map(|recorder| (id, recorder)))
+        };
+
+        let Some((renderer_id, recorder)) = recorder else {
+            return Err(exceptions::PyRuntimeError::new_err(format!(
+                "'{video_source}' of participant '{participant_id}' is not being recorded"
+            )));
+        };
+
+        // Native renderers can't be removed, so give the video source back to
+        // the renderer it had before the recording started, if any.
+        let previous_renderer = {
+            let mut registrations = self.inner.renderer_registrations.lock().unwrap();
+
+            registrations.remove(&renderer_id);
+
+            registrations
+                .iter()
+                .filter(|(_, registration)| {
+                    matches!(
+                        registration,
+                        RendererRegistration::Video {
+                            participant_id: p,
+                            video_source: s,
+                            ..
+                        } if p == participant_id && s == video_source
+                    )
+                })
+                .max_by_key(|(renderer_id, _)| **renderer_id)
+                .map(|(renderer_id, registration)| (*renderer_id, registration.clone()))
+        };
+
+        if let Some((renderer_id, registration)) = previous_renderer {
+            set_native_renderer(&mut call_client, renderer_id, registration);
+        }
+
+        // Wait for the pending frames to be written without holding the GIL.
+        let path = recorder.path().to_string();
+        py.allow_threads(move || recorder.finish())
+            .map_err(|error| {
+                exceptions::PyIOError::new_err(format!(
+                    "unable to finish local video recording '{path}': {error}"
+                ))
+            })
+    }
 }
 
 impl Drop for PyCallClient {
@@ -1143,6 +3572,15 @@ impl Drop for PyCallClient {
     }
 }
 
+unsafe fn get_participants(call_client: &mut CallClient) -> Value {
+    let participants_ptr = daily_core_call_client_participants(call_client);
+    let participants_string = CStr::from_ptr(participants_ptr)
+        .to_string_lossy()
+        .into_owned();
+
+    serde_json::from_str(participants_string.as_str()).unwrap()
+}
+
 unsafe fn get_active_speaker(call_client: &mut CallClient) -> PyResult<PyObject> {
     let active_speaker_ptr = daily_core_call_client_active_speaker(call_client);
     let active_speaker_string = CStr::from_ptr(active_speaker_ptr)
@@ -0,0 +1,628 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::delegate::PyCallClientInner;
+
+use crate::util::checksum::crc32;
+
+// The key that identifies app messages that are chunks of a larger message.
+const CHUNK_KEY: &str = "dailyChunk";
+
+// Incomplete transfers are discarded if no chunks are received for this time.
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Transfers larger than this are rejected.
+pub(crate) const MAX_TRANSFER_SIZE: usize = 64 * 1024 * 1024;
+
+// The smallest chunk size (of the serialized app message) allowed. It leaves
+// room for the chunk header and at least `MIN_CHUNK_DATA` bytes of the
+// message, even if all of them need to be escaped.
+pub(crate) const MIN_CHUNK_SIZE: usize = 1024;
+
+// Every chunk but the last one carries at least this number of bytes of the
+// message. Receivers use it to bound the number of chunks of a transfer.
+const MIN_CHUNK_DATA: usize = 128;
+
+// The maximum number of incomplete transfers per sender and in total.
+const MAX_TRANSFERS_PER_SENDER: usize = 8;
+const MAX_TRANSFERS: usize = 64;
+
+// The maximum number of bytes buffered for all incomplete transfers.
+const MAX_BUFFERED_SIZE: usize = 2 * MAX_TRANSFER_SIZE;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ChunkHeader {
+    id: String,
+    index: usize,
+    count: usize,
+    size: usize,
+    crc32: u32,
+}
+
+impl ChunkHeader {
+    fn from_value(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+        Some(Self {
+            id: object.get("id")?.as_str()?.to_string(),
+            index: object.get("index")?.as_u64()? as usize,
+            count: object.get("count")?.as_u64()? as usize,
+            size: object.get("size")?.as_u64()? as usize,
+            crc32: u32::try_from(object.get("crc32")?.as_u64()?).ok()?,
+        })
+    }
+}
+
+fn chunk_value(header: &ChunkHeader, data: &str) -> Value {
+    json!({
+        CHUNK_KEY: {
+            "id": header.id,
+            "index": header.index,
+            "count": header.count,
+            "size": header.size,
+            "crc32": header.crc32,
+        },
+        "data": data,
+    })
+}
+
+// The number of bytes the given character takes in a JSON string.
+fn escaped_len(c: char) -> usize {
+    match c {
+        '"' | '\\' | '\n' | '\r' | '\t' | '\u{8}' | '\u{c}' => 2,
+        c if (c as u32) < 0x20 => 6,
+        c => c.len_utf8(),
+    }
+}
+
+/// Splits the given serialized message into app messages whose serialization
+/// is at most `chunk_size` bytes (which should be at least `MIN_CHUNK_SIZE`).
+/// Returns the transfer ID and the chunks.
+pub(crate) fn split_message(payload: &str, chunk_size: usize) -> (String, Vec<Value>) {
+    let mut header = ChunkHeader {
+        id: Uuid::new_v4().to_string(),
+        index: payload.len(),
+        count: payload.len(),
+        size: payload.len(),
+        crc32: u32::MAX,
+    };
+
+    // The chunk size without data. Indices and counts are never larger than
+    // the payload size, so this is the largest overhead.
+    let overhead = serde_json::to_string(&chunk_value(&header, ""))
+        .unwrap()
+        .len();
+    let max_data = chunk_size.saturating_sub(overhead).max(1);
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+    for (index, c) in payload.char_indices() {
+        let c_len = escaped_len(c);
+        // Send characters that don't fit in a chunk on their own.
+        if len > 0 && len + c_len > max_data {
+            parts.push(&payload[start..index]);
+            start = index;
+            len = 0;
+        }
+        len += c_len;
+    }
+    if start < payload.len() {
+        parts.push(&payload[start..]);
+    }
+
+    header.count = parts.len();
+    header.crc32 = crc32(payload.as_bytes());
+
+    let chunks = parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, data)| {
+            chunk_value(
+                &ChunkHeader {
+                    index,
+                    ..header.clone()
+                },
+                data,
+            )
+        })
+        .collect();
+
+    (header.id, chunks)
+}
+
+struct Transfer {
+    header: ChunkHeader,
+    chunks: HashMap<usize, String>,
+    received_size: usize,
+    last_received: Instant,
+}
+
+/// Reassembles chunked app messages, per sender and transfer.
+#[derive(Default)]
+pub(crate) struct ChunkAssembler {
+    transfers: HashMap<(String, String), Transfer>,
+    buffered_size: usize,
+    expiry_scheduled: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum ChunkStatus {
+    Started,
+    Pending,
+    Complete(Value),
+    Invalid(String),
+}
+
+impl ChunkAssembler {
+    fn add(&mut self, sender: &str, header: ChunkHeader, data: &str, now: Instant) -> ChunkStatus {
+        self.expire(now);
+
+        if header.count == 0
+            || header.index >= header.count
+            || header.size > MAX_TRANSFER_SIZE
+            || header.count > header.size.div_ceil(MIN_CHUNK_DATA).max(1)
+        {
+            return ChunkStatus::Invalid(format!("invalid chunk header {header:?}"));
+        }
+
+        let key = (sender.to_string(), header.id.clone());
+
+        let started = !self.transfers.contains_key(&key);
+
+        if started {
+            let sender_transfers = self.transfers.keys().filter(|(s, _)| s == sender).count();
+            if sender_transfers >= MAX_TRANSFERS_PER_SENDER || self.transfers.len() >= MAX_TRANSFERS
+            {
+                return ChunkStatus::Invalid(format!(
+                    "too many incomplete transfers, rejecting transfer {}",
+                    header.id
+                ));
+            }
+        }
+
+        let transfer = self
+            .transfers
+            .entry(key.clone())
+            .or_insert_with(|| Transfer {
+                header: ChunkHeader {
+                    index: 0,
+                    ..header.clone()
+                },
+                chunks: HashMap::new(),
+                received_size: 0,
+                last_received: now,
+            });
+
+        if transfer.header.count != header.count
+            || transfer.header.size != header.size
+            || transfer.header.crc32 != header.crc32
+        {
+            self.remove(&key);
+            return ChunkStatus::Invalid(format!(
+                "inconsistent chunk header for transfer {}",
+                header.id
+            ));
+        }
+
+        transfer.last_received = now;
+
+        if !transfer.chunks.contains_key(&header.index) {
+            if transfer.received_size + data.len() > header.size
+                || self.buffered_size + data.len() > MAX_BUFFERED_SIZE
+            {
+                self.remove(&key);
+                return ChunkStatus::Invalid(format!(
+                    "too much data received for transfer {}",
+                    header.id
+                ));
+            }
+            transfer.chunks.insert(header.index, data.to_string());
+            transfer.received_size += data.len();
+            self.buffered_size += data.len();
+        }
+
+        if transfer.chunks.len() < transfer.header.count {
+            return if started {
+                ChunkStatus::Started
+            } else {
+                ChunkStatus::Pending
+            };
+        }
+
+        let mut transfer = self.remove(&key).unwrap();
+
+        let payload: String = (0..header.count)
+            .filter_map(|index| transfer.chunks.remove(&index))
+            .collect();
+
+        if payload.len() != header.size || crc32(payload.as_bytes()) != header.crc32 {
+            return ChunkStatus::Invalid(format!(
+                "integrity check failed for transfer {}",
+                header.id
+            ));
+        }
+
+        match serde_json::from_str(&payload) {
+            Ok(message) => ChunkStatus::Complete(message),
+            Err(error) => ChunkStatus::Invalid(format!(
+                "invalid message in transfer {}: {error}",
+                header.id
+            )),
+        }
+    }
+
+    fn remove(&mut self, key: &(String, String)) -> Option<Transfer> {
+        let transfer = self.transfers.remove(key)?;
+        self.buffered_size -= transfer.received_size;
+        Some(transfer)
+    }
+
+    // Discards the transfers that haven't received any chunks for
+    // `TRANSFER_TIMEOUT`. Returns the sender and ID of each of them.
+    fn expire(&mut self, now: Instant) -> Vec<(String, String)> {
+        let expired: Vec<_> = self
+            .transfers
+            .iter()
+            .filter(|(_, transfer)| now.duration_since(transfer.last_received) >= TRANSFER_TIMEOUT)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in &expired {
+            self.remove(key);
+        }
+
+        expired
+    }
+}
+
+// Periodically discards the incomplete transfers, until there are none left.
+fn schedule_expiry(inner: &Arc<PyCallClientInner>) {
+    let inner_weak = Arc::downgrade(inner);
+    inner.timer.schedule(TRANSFER_TIMEOUT, move || {
+        let Some(inner) = inner_weak.upgrade() else {
+            return;
+        };
+
+        let reschedule = {
+            let mut chunks = inner.app_message_chunks.lock().unwrap();
+
+            for (sender, id) in chunks.expire(Instant::now()) {
+                tracing::warn!("discarding incomplete app message transfer {id} from {sender}");
+            }
+
+            chunks.expiry_scheduled = !chunks.transfers.is_empty();
+            chunks.expiry_scheduled
+        };
+
+        if reschedule {
+            schedule_expiry(&inner);
+        }
+    });
+}
+
+/// Handles an incoming app message if it's a chunk of a larger message.
+/// Returns the message that should be delivered, if any: the given message if
+/// it's not a chunk or the reassembled message if this was the last chunk.
+pub(crate) fn handle_app_message(
+    inner: &Arc<PyCallClientInner>,
+    message: Value,
+    sender: &Value,
+) -> Option<Value> {
+    let header = message.get(CHUNK_KEY).and_then(ChunkHeader::from_value);
+    let data = message.get("data").and_then(Value::as_str);
+
+    let (Some(header), Some(data), Some(sender)) = (header, data, sender.as_str()) else {
+        return Some(message);
+    };
+
+    let (status, schedule) = {
+        let mut chunks = inner.app_message_chunks.lock().unwrap();
+
+        let status = chunks.add(sender, header, data, Instant::now());
+
+        let schedule = status == ChunkStatus::Started && !chunks.expiry_scheduled;
+        if schedule {
+            chunks.expiry_scheduled = true;
+        }
+
+        (status, schedule)
+    };
+
+    // Don't schedule while holding the lock, expiring transfers takes it from
+    // the timer thread.
+    if schedule {
+        schedule_expiry(inner);
+    }
+
+    match status {
+        ChunkStatus::Started | ChunkStatus::Pending => None,
+        ChunkStatus::Complete(message) => Some(message),
+        ChunkStatus::Invalid(error) => {
+            tracing::warn!("discarding app message transfer from {sender}: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "sender";
+
+    fn split(message: &Value, chunk_size: usize) -> Vec<(ChunkHeader, String)> {
+        let payload = serde_json::to_string(message).unwrap();
+        let (_, chunks) = split_message(&payload, chunk_size);
+        chunks
+            .into_iter()
+            .map(|chunk| {
+                let header = ChunkHeader::from_value(&chunk[CHUNK_KEY]).unwrap();
+                (header, chunk["data"].as_str().unwrap().to_string())
+            })
+            .collect()
+    }
+
+    fn header(id: &str, index: usize, count: usize, size: usize) -> ChunkHeader {
+        ChunkHeader {
+            id: id.to_string(),
+            index,
+            count,
+            size,
+            crc32: 0,
+        }
+    }
+
+    fn large_message() -> Value {
+        json!({
+            "text": "quotes \" and \\ backslashes\n".repeat(200),
+            "control": "\u{1}\u{2}\u{3}".repeat(100),
+            "unicode": "héllo 👋 ".repeat(200),
+        })
+    }
+
+    #[test]
+    fn split_fits_serialized_chunks() {
+        let payload = serde_json::to_string(&large_message()).unwrap();
+        let (_, chunks) = split_message(&payload, MIN_CHUNK_SIZE);
+
+        assert!(chunks.len() > 1);
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert!(serde_json::to_string(chunk).unwrap().len() <= MIN_CHUNK_SIZE);
+            if index < chunks.len() - 1 {
+                assert!(chunk["data"].as_str().unwrap().len() >= MIN_CHUNK_DATA);
+            }
+        }
+    }
+
+    #[test]
+    fn split_worst_case_escaping() {
+        let payload = "\u{1}".repeat(10_000);
+        let (_, chunks) = split_message(&payload, MIN_CHUNK_SIZE);
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert!(serde_json::to_string(chunk).unwrap().len() <= MIN_CHUNK_SIZE);
+            if index < chunks.len() - 1 {
+                assert!(chunk["data"].as_str().unwrap().len() >= MIN_CHUNK_DATA);
+            }
+        }
+    }
+
+    #[test]
+    fn reassemble_in_order() {
+        let message = large_message();
+        let chunks = split(&message, MIN_CHUNK_SIZE);
+        let count = chunks.len();
+
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+        for (index, (header, data)) in chunks.into_iter().enumerate() {
+            let status = assembler.add(SENDER, header, &data, now);
+            match index {
+                0 => assert_eq!(status, ChunkStatus::Started),
+                i if i == count - 1 => assert_eq!(status, ChunkStatus::Complete(message.clone())),
+                _ => assert_eq!(status, ChunkStatus::Pending),
+            }
+        }
+        assert!(assembler.transfers.is_empty());
+        assert_eq!(assembler.buffered_size, 0);
+    }
+
+    #[test]
+    fn reassemble_out_of_order() {
+        let message = large_message();
+        let mut chunks = split(&message, MIN_CHUNK_SIZE);
+        chunks.reverse();
+
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+        let statuses: Vec<_> = chunks
+            .into_iter()
+            .map(|(header, data)| assembler.add(SENDER, header, &data, now))
+            .collect();
+
+        assert_eq!(statuses.last(), Some(&ChunkStatus::Complete(message)));
+    }
+
+    #[test]
+    fn duplicate_chunks_are_ignored() {
+        let message = large_message();
+        let chunks = split(&message, MIN_CHUNK_SIZE);
+
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+        let (first_header, first_data) = chunks[0].clone();
+        assembler.add(SENDER, first_header.clone(), &first_data, now);
+        assert_eq!(
+            assembler.add(SENDER, first_header, &first_data, now),
+            ChunkStatus::Pending
+        );
+
+        let status = chunks
+            .into_iter()
+            .skip(1)
+            .map(|(header, data)| assembler.add(SENDER, header, &data, now))
+            .last();
+        assert_eq!(status, Some(ChunkStatus::Complete(message)));
+    }
+
+    #[test]
+    fn mismatched_header_discards_transfer() {
+        let chunks = split(&large_message(), MIN_CHUNK_SIZE);
+
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+        let (header, data) = chunks[0].clone();
+        assembler.add(SENDER, header, &data, now);
+
+        let (mut header, data) = chunks[1].clone();
+        header.crc32 ^= 1;
+        assert!(matches!(
+            assembler.add(SENDER, header, &data, now),
+            ChunkStatus::Invalid(_)
+        ));
+        assert!(assembler.transfers.is_empty());
+        assert_eq!(assembler.buffered_size, 0);
+    }
+
+    #[test]
+    fn same_transfer_id_from_other_sender_is_separate() {
+        let message = large_message();
+        let chunks = split(&message, MIN_CHUNK_SIZE);
+
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+        let (header, data) = chunks[0].clone();
+        assembler.add("other", header, &data, now);
+
+        let status = chunks
+            .into_iter()
+            .map(|(header, data)| assembler.add(SENDER, header, &data, now))
+            .last();
+        assert_eq!(status, Some(ChunkStatus::Complete(message)));
+        assert_eq!(assembler.transfers.len(), 1);
+    }
+
+    #[test]
+    fn corrupted_data_fails_integrity_check() {
+        let message = json!({"text": "hello"});
+        let mut chunks = split(&message, MIN_CHUNK_SIZE);
+        let (header, data) = chunks.remove(0);
+        let corrupted = data.replace("hello", "hellO");
+
+        let mut assembler = ChunkAssembler::default();
+        assert!(matches!(
+            assembler.add(SENDER, header, &corrupted, Instant::now()),
+            ChunkStatus::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+
+        let invalid = [
+            // No chunks.
+            header("a", 0, 0, 10),
+            // Index out of range.
+            header("b", 2, 2, 1000),
+            // Too large.
+            header("c", 0, 1, MAX_TRANSFER_SIZE + 1),
+            // Too many chunks for the size (this would allocate a lot).
+            header("d", 0, usize::MAX, 1000),
+            header("e", 0, 1000 / MIN_CHUNK_DATA + 2, 1000),
+        ];
+
+        for header in invalid {
+            assert!(matches!(
+                assembler.add(SENDER, header, "x", now),
+                ChunkStatus::Invalid(_)
+            ));
+        }
+        assert!(assembler.transfers.is_empty());
+    }
+
+    #[test]
+    fn oversized_chunks_are_rejected() {
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+
+        assert_eq!(
+            assembler.add(SENDER, header("a", 0, 2, 300), &"x".repeat(200), now),
+            ChunkStatus::Started
+        );
+        assert!(matches!(
+            assembler.add(SENDER, header("a", 1, 2, 300), &"x".repeat(200), now),
+            ChunkStatus::Invalid(_)
+        ));
+        assert!(assembler.transfers.is_empty());
+        assert_eq!(assembler.buffered_size, 0);
+    }
+
+    #[test]
+    fn open_transfers_are_limited() {
+        let mut assembler = ChunkAssembler::default();
+        let now = Instant::now();
+
+        for i in 0..MAX_TRANSFERS_PER_SENDER {
+            assert_eq!(
+                assembler.add(SENDER, header(&i.to_string(), 0, 2, 1000), "x", now),
+                ChunkStatus::Started
+            );
+        }
+        assert!(matches!(
+            assembler.add(SENDER, header("last", 0, 2, 1000), "x", now),
+            ChunkStatus::Invalid(_)
+        ));
+
+        // Other senders can still start transfers, up to the total limit.
+        let mut sender = 0;
+        while assembler.transfers.len() < MAX_TRANSFERS {
+            let status = assembler.add(&sender.to_string(), header("a", 0, 2, 1000), "x", now);
+            assert_eq!(status, ChunkStatus::Started);
+            sender += 1;
+        }
+        assert!(matches!(
+            assembler.add("new", header("a", 0, 2, 1000), "x", now),
+            ChunkStatus::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn idle_transfers_expire() {
+        let mut assembler = ChunkAssembler::default();
+        let start = Instant::now();
+
+        assembler.add(SENDER, header("idle", 0, 2, 1000), "x", start);
+        assembler.add(SENDER, header("active", 0, 3, 1000), "x", start);
+        assembler.add(
+            SENDER,
+            header("active", 1, 3, 1000),
+            "x",
+            start + TRANSFER_TIMEOUT / 2,
+        );
+
+        let expired = assembler.expire(start + TRANSFER_TIMEOUT);
+        assert_eq!(expired, vec![(SENDER.to_string(), "idle".to_string())]);
+        assert_eq!(assembler.transfers.len(), 1);
+        assert_eq!(assembler.buffered_size, 2);
+
+        // Chunks are expired as new ones arrive too.
+        assert_eq!(
+            assembler.add(
+                SENDER,
+                header("new", 0, 2, 1000),
+                "x",
+                start + TRANSFER_TIMEOUT * 2
+            ),
+            ChunkStatus::Started
+        );
+        assert_eq!(assembler.transfers.len(), 1);
+    }
+}
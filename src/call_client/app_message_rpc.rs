@@ -0,0 +1,192 @@
+use std::{collections::HashMap, sync::mpsc};
+
+use pyo3::prelude::*;
+use serde_json::{Map, Value};
+
+use super::delegate::PyCallClientInner;
+
+use crate::{util::dict::DictValue, GLOBAL_CONTEXT};
+
+// The key that identifies app messages that are part of a request/response
+// exchange.
+const RPC_KEY: &str = "dailyRpc";
+
+pub(crate) type RpcResult = Result<Value, String>;
+
+/// An app message that is part of a request/response exchange. Requests look
+/// like `{"dailyRpc": "request", "id": ..., "method": ..., "data": ...}` and
+/// responses like `{"dailyRpc": "response", "id": ..., "data": ...}` or
+/// `{"dailyRpc": "response", "id": ..., "error": ...}`.
+pub(crate) enum RpcMessage {
+    Request {
+        id: String,
+        method: Option<String>,
+        data: Value,
+    },
+    Response {
+        id: String,
+        result: RpcResult,
+    },
+}
+
+impl RpcMessage {
+    pub fn from_value(message: &Value) -> Option<Self> {
+        let object = message.as_object()?;
+
+        let id = object.get("id")?.as_str()?.to_string();
+        let data = object.get("data").cloned().unwrap_or(Value::Null);
+
+        match object.get(RPC_KEY)?.as_str()? {
+            "request" => Some(RpcMessage::Request {
+                id,
+                method: object
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                data,
+            }),
+            "response" => {
+                let result = match object.get("error") {
+                    None | Some(Value::Null) => Ok(data),
+                    Some(Value::String(error)) => Err(error.clone()),
+                    Some(error) => Err(error.to_string()),
+                };
+                Some(RpcMessage::Response { id, result })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        let mut object = Map::new();
+
+        match self {
+            RpcMessage::Request { id, method, data } => {
+                object.insert(RPC_KEY.to_string(), "request".into());
+                object.insert("id".to_string(), id.clone().into());
+                if let Some(method) = method {
+                    object.insert("method".to_string(), method.clone().into());
+                }
+                object.insert("data".to_string(), data.clone());
+            }
+            RpcMessage::Response { id, result } => {
+                object.insert(RPC_KEY.to_string(), "response".into());
+                object.insert("id".to_string(), id.clone().into());
+                match result {
+                    Ok(data) => object.insert("data".to_string(), data.clone()),
+                    Err(error) => object.insert("error".to_string(), error.clone().into()),
+                };
+            }
+        }
+
+        Value::Object(object)
+    }
+}
+
+/// The registered request handlers and the requests waiting for a response.
+#[derive(Default)]
+pub(crate) struct AppMessageRpc {
+    handlers: HashMap<String, PyObject>,
+    pending: HashMap<String, mpsc::Sender<RpcResult>>,
+}
+
+impl AppMessageRpc {
+    pub fn set_handler(&mut self, method: &str, handler: Option<PyObject>) {
+        if let Some(handler) = handler {
+            self.handlers.insert(method.to_string(), handler);
+        } else {
+            self.handlers.remove(method);
+        }
+    }
+
+    pub fn add_pending(&mut self, id: &str, sender: mpsc::Sender<RpcResult>) {
+        self.pending.insert(id.to_string(), sender);
+    }
+
+    pub fn remove_pending(&mut self, id: &str) {
+        self.pending.remove(id);
+    }
+
+    /// Drops all handlers and pending requests. Pending requests get woken up.
+    pub fn clear(&mut self) {
+        self.handlers.clear();
+        self.pending.clear();
+    }
+}
+
+/// Handles an incoming app message if it's part of a request/response
+/// exchange. Returns whether the message was handled, in which case it
+/// shouldn't be delivered as a regular app message.
+///
+/// Requests are only answered if there's a handler for their method, so
+/// clients without one don't race with the one that has it. Handlers are
+/// called on the event thread holding the GIL, so no other events are
+/// delivered until they return.
+pub(crate) fn handle_app_message(
+    py: Python<'_>,
+    inner: &PyCallClientInner,
+    message: &Value,
+    sender: &Value,
+) -> bool {
+    match RpcMessage::from_value(message) {
+        Some(RpcMessage::Response { id, result }) => {
+            // Don't lock in the if statement otherwise the lock is held while
+            // sending.
+            let pending = inner.app_message_rpc.lock().unwrap().pending.remove(&id);
+            if let Some(pending) = pending {
+                // The requester might be gone (e.g. it timed out).
+                let _ = pending.send(result);
+            }
+            true
+        }
+        Some(RpcMessage::Request { id, method, data }) => {
+            // We can't reply to requests without a sender.
+            let Some(sender_id) = sender.as_str() else {
+                return false;
+            };
+
+            let handler = method.as_ref().and_then(|method| {
+                inner
+                    .app_message_rpc
+                    .lock()
+                    .unwrap()
+                    .handlers
+                    .get(method)
+                    .cloned()
+            });
+
+            let Some(handler) = handler else {
+                tracing::debug!(
+                    "ignoring app message request {id} from {sender_id}, no handler for method '{}'",
+                    method.unwrap_or_default()
+                );
+                return true;
+            };
+
+            let result = handler
+                .call1(py, (DictValue(data).to_object_with_binary(py), sender_id))
+                .and_then(|result| DictValue::extract_with_binary(result.bind(py)))
+                .map(|result| result.0)
+                .map_err(|error| error.to_string());
+
+            let sent = GLOBAL_CONTEXT
+                .call_client(inner.call_client_id)
+                .is_some_and(|call_client| {
+                    let response = RpcMessage::Response {
+                        id: id.clone(),
+                        result,
+                    };
+                    call_client.send_app_message(&response.to_value(), Some(sender_id))
+                });
+
+            if !sent {
+                tracing::warn!(
+                    "dropping app message response {id} to {sender_id}, the call client has been released"
+                );
+            }
+
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use serde_json::{Map, Value};
+
+use super::delegate::PyCallClientInner;
+
+use crate::util::dict::DictValue;
+
+// The key that identifies app messages sent to a topic.
+const TOPIC_KEY: &str = "dailyTopic";
+
+/// Wraps the given message so it's delivered to the subscribers of `topic`.
+/// Topic messages look like `{"dailyTopic": ..., "data": ...}`.
+pub(crate) fn wrap_message(topic: &str, message: Value) -> Value {
+    let mut object = Map::new();
+    object.insert(TOPIC_KEY.to_string(), topic.into());
+    object.insert("data".to_string(), message);
+    Value::Object(object)
+}
+
+struct Subscription {
+    topic: String,
+    callback: PyObject,
+}
+
+/// The app message topic subscriptions, by subscription ID.
+#[derive(Default)]
+pub(crate) struct AppMessageTopics {
+    subscriptions: HashMap<u64, Subscription>,
+    next_subscription_id: u64,
+}
+
+impl AppMessageTopics {
+    /// Subscribes the given callback to `topic` and returns the subscription
+    /// ID. IDs are increasing, so they also keep the subscription order.
+    pub fn subscribe(&mut self, topic: &str, callback: PyObject) -> u64 {
+        let subscription_id = self.next_subscription_id;
+        self.next_subscription_id += 1;
+
+        self.subscriptions.insert(
+            subscription_id,
+            Subscription {
+                topic: topic.to_string(),
+                callback,
+            },
+        );
+
+        subscription_id
+    }
+
+    pub fn unsubscribe(&mut self, subscription_id: u64) -> bool {
+        self.subscriptions.remove(&subscription_id).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.subscriptions.clear();
+    }
+
+    fn callbacks(&self, topic: &str) -> Vec<PyObject> {
+        let mut subscriptions: Vec<_> = self
+            .subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.topic == topic)
+            .collect();
+
+        // Call subscribers in the order they subscribed.
+        subscriptions.sort_by_key(|(subscription_id, _)| **subscription_id);
+
+        subscriptions
+            .into_iter()
+            .map(|(_, subscription)| subscription.callback.clone())
+            .collect()
+    }
+}
+
+/// Handles an incoming app message if it was sent to a topic with local
+/// subscribers. Returns whether the message was handled, in which case it
+/// shouldn't be delivered as a regular app message.
+pub(crate) fn handle_app_message(
+    py: Python<'_>,
+    inner: &PyCallClientInner,
+    message: &Value,
+    sender: &Value,
+) -> bool {
+    let Some(topic) = message.get(TOPIC_KEY).and_then(Value::as_str) else {
+        return false;
+    };
+
+    // Don't hold the lock while calling the subscribers, they might
+    // (un)subscribe.
+    let callbacks = inner.app_message_topics.lock().unwrap().callbacks(topic);
+
+    if callbacks.is_empty() {
+        return false;
+    }
+
+    let data =
+        DictValue(message.get("data").cloned().unwrap_or(Value::Null)).to_object_with_binary(py);
+
+    for callback in callbacks {
+        if let Err(error) = callback.call1(py, (data.clone_ref(py), sender.as_str())) {
+            error.write_unraisable_bound(py, None);
+        }
+    }
+
+    true
+}
@@ -1,7 +1,8 @@
 use std::{
     collections::HashMap,
     ffi::CStr,
-    sync::{Arc, Mutex},
+    sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use pyo3::{
@@ -11,23 +12,91 @@ use pyo3::{
 
 use daily_core::prelude::*;
 
+use super::app_message_chunks::{self, ChunkAssembler};
+use super::app_message_rpc::{self, AppMessageRpc};
+use super::app_message_topics::{self, AppMessageTopics};
+use super::event_handler::EventHandlerRef;
+use super::network_quality::NetworkQualityMonitor;
+use super::network_stats::NetworkStatsHistory;
+use super::prebuilt_chat::PrebuiltChatMessage;
+use super::reconnect::{self, ReconnectReason, Reconnector, RendererRegistration};
+use super::streaming::StreamingTracker;
+use super::transcript::TranscriptAssembler;
+use super::waiter::{self, Waiter};
+use super::SyncCompletion;
+
 use super::event::{
-    args_from_event, completion_args_from_event, method_name_from_event_action,
-    request_id_from_event, update_inner_values, Event,
+    args_from_event, completion_args_from_event, completion_result_from_event,
+    method_name_from_event_action, request_id_from_event, update_inner_values, Event,
+};
+
+use crate::{
+    media::video_recorder::{RecordingError, VideoRecorder},
+    metrics::{CallClientMetrics, NetworkMetrics},
+    util::{
+        audio::AudioLevelMeter, dict::DictValue, memory::AlignedI16Data, time::monotonic_time_us,
+        timer::Timer,
+    },
+    PyAudioData, PyVideoFrame,
 };
 
-use crate::{PyAudioData, PyVideoFrame};
+pub(crate) type CompletionResult = Result<serde_json::Value, String>;
+
+/// Errors of requests that are completed locally instead of by the request
+/// itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RequestError {
+    TimedOut,
+    Cancelled,
+}
+
+impl RequestError {
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            RequestError::TimedOut => "request timed out",
+            RequestError::Cancelled => "request cancelled",
+        }
+    }
+}
 
 pub(crate) enum PyCallClientCompletion {
     UnaryFn(PyObject),
     BinaryFn(PyObject),
+    // Completions that are waited on from Rust (e.g. when shutting down)
+    // instead of calling into Python.
+    Channel(mpsc::Sender<CompletionResult>),
+    // Rejoins started by the reconnect policy.
+    Reconnect,
 }
 
-impl From<PyCallClientCompletion> for PyObject {
-    fn from(value: PyCallClientCompletion) -> Self {
-        match value {
-            PyCallClientCompletion::UnaryFn(c) => c,
-            PyCallClientCompletion::BinaryFn(c) => c,
+impl PyCallClientCompletion {
+    /// Completes the request with the given error. This is used when requests
+    /// are completed locally (e.g. they time out or are cancelled).
+    pub(crate) fn complete_with_error(self, py: Python<'_>, error: RequestError) {
+        // Blocking requests get the error itself, not just its message.
+        if let PyCallClientCompletion::UnaryFn(callback)
+        | PyCallClientCompletion::BinaryFn(callback) = &self
+        {
+            if let Ok(completion) = callback.bind(py).downcast::<SyncCompletion>() {
+                completion.borrow().complete_with_error(error);
+                return;
+            }
+        }
+
+        let error = error.message();
+
+        let result = match self {
+            PyCallClientCompletion::UnaryFn(callback) => callback.call1(py, (error,)),
+            PyCallClientCompletion::BinaryFn(callback) => callback.call1(py, (py.None(), error)),
+            PyCallClientCompletion::Channel(sender) => {
+                let _ = sender.send(Err(error.to_string()));
+                return;
+            }
+            PyCallClientCompletion::Reconnect => return,
+        };
+
+        if let Err(error) = result {
+            error.write_unraisable_bound(py, None);
         }
     }
 }
@@ -49,6 +118,7 @@ type PyCallClientDelegateOnAudioDataFn = unsafe fn(
     renderer_id: u64,
     peer_id: *const libc::c_char,
     audio_data: *const NativeAudioData,
+    timestamp_us: i64,
 );
 
 #[derive(Clone)]
@@ -59,11 +129,33 @@ pub(crate) struct PyCallClientDelegateFns {
 }
 
 pub(crate) struct PyCallClientInner {
-    pub(crate) event_handler_callback: Mutex<Option<PyObject>>,
+    pub(crate) call_client_id: u64,
+    pub(crate) event_handler_callback: Mutex<Option<EventHandlerRef>>,
     pub(crate) delegates: Mutex<PyCallClientDelegateFns>,
     pub(crate) completions: Mutex<HashMap<u64, PyCallClientCompletion>>,
     pub(crate) video_renderers: Mutex<HashMap<u64, PyObject>>,
     pub(crate) audio_renderers: Mutex<HashMap<u64, PyObject>>,
+    pub(crate) video_recorders: Mutex<HashMap<u64, VideoRecorder>>,
+    pub(crate) audio_level_observers: Mutex<HashMap<u64, AudioLevelObserver>>,
+    pub(crate) audio_sample_offsets: Mutex<HashMap<u64, u64>>,
+    pub(crate) av_renderers: Mutex<HashMap<u64, AvRenderer>>,
+    pub(crate) av_renderer_ids: Mutex<HashMap<u64, u64>>,
+    pub(crate) meeting_url: Mutex<Option<String>>,
+    pub(crate) call_state: Mutex<String>,
+    pub(crate) metrics: CallClientMetrics,
+    pub(crate) network_stats_history: Mutex<NetworkStatsHistory>,
+    pub(crate) network_quality: Mutex<NetworkQualityMonitor>,
+    pub(crate) waiters: Mutex<HashMap<u64, Waiter>>,
+    pub(crate) app_message_rpc: Mutex<AppMessageRpc>,
+    pub(crate) app_message_chunks: Mutex<ChunkAssembler>,
+    pub(crate) app_message_topics: Mutex<AppMessageTopics>,
+    pub(crate) prebuilt_chat_suppressed: Mutex<bool>,
+    pub(crate) transcript: Mutex<TranscriptAssembler>,
+    pub(crate) recordings: Mutex<StreamingTracker>,
+    pub(crate) live_streams: Mutex<StreamingTracker>,
+    pub(crate) reconnector: Mutex<Reconnector>,
+    pub(crate) renderer_registrations: Mutex<HashMap<u64, RendererRegistration>>,
+    pub(crate) timer: Timer,
     // Non-blocking updates
     pub(crate) active_speaker: Mutex<PyObject>,
     pub(crate) inputs: Mutex<PyObject>,
@@ -74,6 +166,161 @@ pub(crate) struct PyCallClientInner {
     pub(crate) network_stats: Mutex<PyObject>,
 }
 
+impl PyCallClientInner {
+    /// Calls the given event handler method, if there's an event handler.
+    pub(crate) fn emit_event(
+        &self,
+        py: Python<'_>,
+        method_name: &str,
+        args: impl IntoPy<Py<PyTuple>>,
+    ) {
+        // Don't lock in the if statement otherwise the lock is held throughout
+        // the callback call.
+        let callback = self
+            .event_handler_callback
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|callback| callback.get(py));
+
+        if let Some(callback) = callback {
+            if let Err(error) = callback.call_method1(method_name, args) {
+                error.write_unraisable_bound(py, None);
+            }
+        }
+    }
+
+    /// Forgets the audio sample offsets of the renderers whose registration
+    /// matches the given predicate (e.g. because the renderer was replaced).
+    pub(crate) fn remove_audio_sample_offsets(
+        &self,
+        predicate: impl Fn(&RendererRegistration) -> bool,
+    ) {
+        let renderer_ids: Vec<u64> = self
+            .renderer_registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, registration)| predicate(registration))
+            .map(|(renderer_id, _)| *renderer_id)
+            .collect();
+
+        let mut offsets = self.audio_sample_offsets.lock().unwrap();
+        for renderer_id in renderer_ids {
+            offsets.remove(&renderer_id);
+        }
+    }
+}
+
+pub(crate) struct AudioLevelObserver {
+    pub(crate) callback: PyObject,
+    pub(crate) interval: Duration,
+    pub(crate) participant_id: String,
+    pub(crate) audio_source: String,
+    pub(crate) meter: AudioLevelMeter,
+    pub(crate) last_report: Instant,
+}
+
+impl AudioLevelObserver {
+    pub(crate) fn new(
+        callback: PyObject,
+        interval: Duration,
+        participant_id: &str,
+        audio_source: &str,
+    ) -> Self {
+        Self {
+            callback,
+            interval,
+            participant_id: participant_id.to_string(),
+            audio_source: audio_source.to_string(),
+            meter: AudioLevelMeter::default(),
+            last_report: Instant::now(),
+        }
+    }
+}
+
+// If no video frames are received (e.g. the participant's camera is off) A/V
+// renderers still get audio once this amount of audio is pending.
+const AV_RENDERER_MAX_PENDING_AUDIO_MS: usize = 100;
+
+struct PendingAudio {
+    bits_per_sample: u32,
+    sample_rate: u32,
+    num_channels: usize,
+    num_audio_frames: usize,
+    timestamp_us: i64,
+    sample_offset: u64,
+    audio_frames: Vec<u8>,
+}
+
+impl PendingAudio {
+    fn duration_ms(&self) -> usize {
+        (self.num_audio_frames * 1000) / self.sample_rate.max(1) as usize
+    }
+
+    fn into_audio_data(self, py: Python<'_>) -> PyAudioData {
+        PyAudioData {
+            bits_per_sample: self.bits_per_sample,
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels,
+            num_audio_frames: self.num_audio_frames,
+            audio_frames: PyBytes::new_bound(py, &self.audio_frames).into_py(py),
+            timestamp_us: self.timestamp_us,
+            sample_offset: self.sample_offset,
+        }
+    }
+}
+
+/// An A/V renderer receives the video frames of a participant together with
+/// all the audio received since the previous video frame.
+pub(crate) struct AvRenderer {
+    pub(crate) callback: PyObject,
+    pending_audio: Option<PendingAudio>,
+}
+
+impl AvRenderer {
+    pub(crate) fn new(callback: PyObject) -> Self {
+        Self {
+            callback,
+            pending_audio: None,
+        }
+    }
+
+    unsafe fn push_audio(
+        &mut self,
+        data: *const NativeAudioData,
+        timestamp_us: i64,
+        sample_offset: u64,
+    ) {
+        let num_bytes = audio_data_num_bytes(data);
+        let bytes = std::slice::from_raw_parts((*data).audio_frames, num_bytes);
+
+        match self.pending_audio.as_mut() {
+            Some(pending)
+                if pending.bits_per_sample == (*data).bits_per_sample
+                    && pending.sample_rate == (*data).sample_rate
+                    && pending.num_channels == (*data).num_channels =>
+            {
+                pending.num_audio_frames += (*data).num_audio_frames;
+                pending.audio_frames.extend_from_slice(bytes);
+            }
+            _ => {
+                // Nothing pending or the audio format has changed, in which
+                // case we drop the pending audio.
+                self.pending_audio = Some(PendingAudio {
+                    bits_per_sample: (*data).bits_per_sample,
+                    sample_rate: (*data).sample_rate,
+                    num_channels: (*data).num_channels,
+                    num_audio_frames: (*data).num_audio_frames,
+                    timestamp_us,
+                    sample_offset,
+                    audio_frames: bytes.to_vec(),
+                });
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct DelegateContext {
     pub(crate) inner: Arc<PyCallClientInner>,
@@ -85,6 +332,9 @@ pub(crate) struct DelegateContextPtr {
 }
 
 unsafe impl Send for DelegateContextPtr {}
+// The pointer is only used when the call client is released, while holding the
+// call client lock.
+unsafe impl Sync for DelegateContextPtr {}
 
 pub(crate) unsafe extern "C" fn on_event_native(
     delegate: *mut libc::c_void,
@@ -123,25 +373,53 @@ pub(crate) unsafe extern "C" fn on_audio_data_native(
     peer_id: *const libc::c_char,
     audio_data: *const NativeAudioData,
 ) {
+    // Take the timestamp as soon as audio is received, before waiting for any
+    // lock or the GIL. This is the system's monotonic clock, not the clock of
+    // video frame timestamps (which are set by WebRTC).
+    let timestamp_us = monotonic_time_us();
+
+    let delegate_ctx_ptr = delegate as *const DelegateContext;
+
+    // We increment the reference count because otherwise it will get dropped
+    // when Arc::from_raw() takes ownership, and we still want to keep the
+    // delegate pointer around.
+    Arc::increment_strong_count(delegate_ctx_ptr);
+
+    let delegate_ctx = Arc::from_raw(delegate_ctx_ptr);
+
+    delegate_ctx
+        .inner
+        .metrics
+        .add_audio_frames((*audio_data).num_audio_frames as u64);
+
+    // Audio levels are accumulated without the GIL. The GIL is only acquired
+    // when the observer needs to be notified.
+    on_audio_data_level(&delegate_ctx, renderer_id, peer_id, audio_data);
+
+    // Native renderers can't be removed, so audio of renderers that were only
+    // used by audio level observers might keep arriving. Drop it without
+    // acquiring the GIL.
+    if !has_audio_renderer(&delegate_ctx, renderer_id) {
+        return;
+    }
+
     // Acquire the GIL before checking if there's a delegate available. If
     // PyCallClient is dropping it will cleanup the delegates and will
     // temporarily release the GIL so we can proceed.
     Python::with_gil(|py| {
-        let delegate_ctx_ptr = delegate as *const DelegateContext;
-
-        // We increment the reference count because otherwise it will get dropped
-        // when Arc::from_raw() takes ownership, and we still want to keep the
-        // delegate pointer around.
-        Arc::increment_strong_count(delegate_ctx_ptr);
-
-        let delegate_ctx = Arc::from_raw(delegate_ctx_ptr);
-
         // Don't lock in the if statement otherwise the lock is held throughout
         // the delegate call.
         let delegate = delegate_ctx.inner.delegates.lock().unwrap().on_audio_data;
 
         if let Some(delegate) = delegate {
-            delegate(py, &delegate_ctx, renderer_id, peer_id, audio_data);
+            delegate(
+                py,
+                &delegate_ctx,
+                renderer_id,
+                peer_id,
+                audio_data,
+                timestamp_us,
+            );
         }
     });
 }
@@ -152,19 +430,33 @@ pub(crate) unsafe extern "C" fn on_video_frame_native(
     peer_id: *const libc::c_char,
     frame: *const NativeVideoFrame,
 ) {
+    let delegate_ctx_ptr = delegate as *const DelegateContext;
+
+    // We increment the reference count because otherwise it will get dropped
+    // when Arc::from_raw() takes ownership, and we still want to keep the
+    // delegate pointer around.
+    Arc::increment_strong_count(delegate_ctx_ptr);
+
+    let delegate_ctx = Arc::from_raw(delegate_ctx_ptr);
+
+    delegate_ctx.inner.metrics.add_video_frame();
+
+    // Local video recordings don't need Python at all, so handle them before
+    // acquiring the GIL.
+    if on_video_frame_recording(&delegate_ctx, renderer_id, frame) {
+        return;
+    }
+
+    // Native renderers can't be removed, so frames of a stopped local
+    // recording keep arriving. Drop them without acquiring the GIL.
+    if !has_video_renderer(&delegate_ctx, renderer_id) {
+        return;
+    }
+
     // Acquire the GIL before checking if there's a delegate available. If
     // PyCallClient is dropping it will cleanup the delegates and will
     // temporarily release the GIL so we can proceed.
     Python::with_gil(|py| {
-        let delegate_ctx_ptr = delegate as *const DelegateContext;
-
-        // We increment the reference count because otherwise it will get dropped
-        // when Arc::from_raw() takes ownership, and we still want to keep the
-        // delegate pointer around.
-        Arc::increment_strong_count(delegate_ctx_ptr);
-
-        let delegate_ctx = Arc::from_raw(delegate_ctx_ptr);
-
         // Don't lock in the if statement otherwise the lock is held throughout
         // the delegate call.
         let delegate = delegate_ctx.inner.delegates.lock().unwrap().on_video_frame;
@@ -179,6 +471,11 @@ pub(crate) unsafe fn on_event(py: Python<'_>, delegate_ctx: &DelegateContext, ev
     match event.action.as_str() {
         "request-completed" => {
             if let Some(request_id) = request_id_from_event(event) {
+                delegate_ctx
+                    .inner
+                    .metrics
+                    .request_completed(request_id, completion_result_from_event(event).is_ok());
+
                 // Don't lock in the if statement otherwise the lock is held
                 // throughout the callback call.
                 let completion = delegate_ctx
@@ -187,16 +484,32 @@ pub(crate) unsafe fn on_event(py: Python<'_>, delegate_ctx: &DelegateContext, ev
                     .lock()
                     .unwrap()
                     .remove(&request_id);
-                if let Some(completion) = completion {
-                    if let Some(args) = completion_args_from_event(&completion, event) {
-                        let py_args = PyTuple::new_bound(py, args);
-
-                        let callback: PyObject = completion.into();
+                match completion {
+                    Some(PyCallClientCompletion::Channel(sender)) => {
+                        // The receiver might be gone (e.g. it timed out).
+                        let _ = sender.send(completion_result_from_event(event));
+                    }
+                    Some(PyCallClientCompletion::Reconnect) => {
+                        reconnect::on_rejoin_completed(
+                            py,
+                            &delegate_ctx.inner,
+                            completion_result_from_event(event).is_ok(),
+                        );
+                    }
+                    Some(completion) => {
+                        if let Some(args) = completion_args_from_event(&completion, event) {
+                            if let PyCallClientCompletion::UnaryFn(callback)
+                            | PyCallClientCompletion::BinaryFn(callback) = completion
+                            {
+                                let py_args = PyTuple::new_bound(py, args);
 
-                        if let Err(error) = callback.call1(py, py_args) {
-                            error.write_unraisable_bound(py, None);
+                                if let Err(error) = callback.call1(py, py_args) {
+                                    error.write_unraisable_bound(py, None);
+                                }
+                            }
                         }
                     }
+                    None => (),
                 }
             }
         }
@@ -208,14 +521,136 @@ pub(crate) unsafe fn on_event(py: Python<'_>, delegate_ctx: &DelegateContext, ev
                     // use the getters inside the callback.
                     update_inner_values(py, delegate_ctx, action, args.clone());
 
-                    let callback = delegate_ctx.inner.event_handler_callback.lock().unwrap();
+                    let mut args = args;
+
+                    if action == "app-message" {
+                        // Chunks are only delivered once the whole message has
+                        // been received.
+                        match app_message_chunks::handle_app_message(
+                            &delegate_ctx.inner,
+                            args[0].0.clone(),
+                            &args[1].0,
+                        ) {
+                            Some(message) => args[0] = DictValue(message),
+                            None => return,
+                        }
+
+                        // Requests and responses are not regular app messages.
+                        if app_message_rpc::handle_app_message(
+                            py,
+                            &delegate_ctx.inner,
+                            &args[0].0,
+                            &args[1].0,
+                        ) {
+                            return;
+                        }
+
+                        // Topic messages only go to the topic subscribers, if
+                        // there are any.
+                        if app_message_topics::handle_app_message(
+                            py,
+                            &delegate_ctx.inner,
+                            &args[0].0,
+                            &args[1].0,
+                        ) {
+                            return;
+                        }
+
+                        // Prebuilt chat messages have their own event.
+                        if let Some(chat) = PrebuiltChatMessage::from_value(&args[0].0) {
+                            delegate_ctx.inner.emit_event(
+                                py,
+                                "on_prebuilt_chat_message",
+                                (
+                                    chat.message,
+                                    chat.user_name,
+                                    args[1].0.as_str(),
+                                    chat.timestamp,
+                                ),
+                            );
+
+                            if *delegate_ctx.inner.prebuilt_chat_suppressed.lock().unwrap() {
+                                return;
+                            }
+                        }
+                    }
+
+                    // Network quality is evaluated natively and only notified
+                    // when it changes.
+                    let quality_changed = if action == "network-stats-updated" {
+                        let network = NetworkMetrics::from_stats(&args.first().unwrap().0);
+                        delegate_ctx
+                            .inner
+                            .network_quality
+                            .lock()
+                            .unwrap()
+                            .update(&network)
+                    } else {
+                        None
+                    };
+
+                    // Wake up anyone waiting for this event.
+                    waiter::notify_waiters(
+                        &delegate_ctx.inner.waiters,
+                        action,
+                        &args.first().unwrap().0,
+                    );
+
+                    // Not all errors make us leave the meeting, so errors
+                    // are only remembered as the reason of the next
+                    // disconnection.
+                    let disconnected = match action {
+                        "call-state-updated" => args.first().unwrap().0.as_str() == Some("left"),
+                        "error" => {
+                            delegate_ctx
+                                .inner
+                                .reconnector
+                                .lock()
+                                .unwrap()
+                                .error_received(ReconnectReason::from_error_event(&event.data.0));
+                            false
+                        }
+                        _ => false,
+                    };
+
+                    // Don't lock in the if statement otherwise the lock is held
+                    // throughout the callback call.
+                    let callback = delegate_ctx
+                        .inner
+                        .event_handler_callback
+                        .lock()
+                        .unwrap()
+                        .as_ref()
+                        .and_then(|callback| callback.get(py));
 
-                    if let Some(callback) = callback.as_ref() {
-                        let py_args = PyTuple::new_bound(py, args);
+                    if let Some(callback) = callback {
+                        // Binary data in app messages is delivered as bytes.
+                        let py_args = if action == "app-message" {
+                            PyTuple::new_bound(
+                                py,
+                                args.iter().map(|arg| arg.to_object_with_binary(py)),
+                            )
+                        } else {
+                            PyTuple::new_bound(py, args)
+                        };
 
-                        if let Err(error) = callback.call_method1(py, method_name, py_args) {
+                        if let Err(error) = callback.call_method1(method_name, py_args) {
                             error.write_unraisable_bound(py, None);
                         }
+
+                        if let Some((quality, reasons)) = quality_changed {
+                            let py_args = (quality.as_str(), reasons);
+
+                            if let Err(error) =
+                                callback.call_method1("on_network_quality_changed", py_args)
+                            {
+                                error.write_unraisable_bound(py, None);
+                            }
+                        }
+                    }
+
+                    if disconnected {
+                        reconnect::on_disconnected(py, &delegate_ctx.inner);
                     }
                 }
             }
@@ -229,7 +664,39 @@ pub(crate) unsafe fn on_audio_data(
     renderer_id: u64,
     peer_id: *const libc::c_char,
     data: *const NativeAudioData,
+    timestamp_us: i64,
 ) {
+    // The offset counts the audio frames delivered to this renderer, it's not
+    // derived from RTP timestamps.
+    let sample_offset = {
+        let mut offsets = delegate_ctx.inner.audio_sample_offsets.lock().unwrap();
+        let offset = offsets.entry(renderer_id).or_insert(0);
+        let current = *offset;
+        *offset += (*data).num_audio_frames as u64;
+        current
+    };
+
+    let av_renderer_id = delegate_ctx
+        .inner
+        .av_renderer_ids
+        .lock()
+        .unwrap()
+        .get(&renderer_id)
+        .copied();
+
+    if let Some(av_renderer_id) = av_renderer_id {
+        on_av_audio_data(
+            py,
+            delegate_ctx,
+            av_renderer_id,
+            peer_id,
+            data,
+            timestamp_us,
+            sample_offset,
+        );
+        return;
+    }
+
     // Don't lock in the if statement otherwise the lock is held throughout the
     // callback call.
     let callback = delegate_ctx
@@ -243,17 +710,7 @@ pub(crate) unsafe fn on_audio_data(
     if let Some(callback) = callback {
         let peer_id = CStr::from_ptr(peer_id).to_string_lossy().into_owned();
 
-        let num_bytes =
-            ((*data).bits_per_sample as usize * (*data).num_channels * (*data).num_audio_frames)
-                / 8;
-
-        let audio_data = PyAudioData {
-            bits_per_sample: (*data).bits_per_sample,
-            sample_rate: (*data).sample_rate,
-            num_channels: (*data).num_channels,
-            num_audio_frames: (*data).num_audio_frames,
-            audio_frames: PyBytes::bound_from_ptr(py, (*data).audio_frames, num_bytes).into_py(py),
-        };
+        let audio_data = audio_data_from_native(py, data, timestamp_us, sample_offset);
 
         let args = PyTuple::new_bound(py, &[peer_id.into_py(py), audio_data.into_py(py)]);
 
@@ -270,6 +727,19 @@ pub(crate) unsafe fn on_video_frame(
     peer_id: *const libc::c_char,
     frame: *const NativeVideoFrame,
 ) {
+    let av_renderer_id = delegate_ctx
+        .inner
+        .av_renderer_ids
+        .lock()
+        .unwrap()
+        .get(&renderer_id)
+        .copied();
+
+    if let Some(av_renderer_id) = av_renderer_id {
+        on_av_video_frame(py, delegate_ctx, av_renderer_id, peer_id, frame);
+        return;
+    }
+
     // Don't lock in the if statement otherwise the lock is held throughout the
     // callback call.
     let callback = delegate_ctx
@@ -283,17 +753,7 @@ pub(crate) unsafe fn on_video_frame(
     if let Some(callback) = callback {
         let peer_id = CStr::from_ptr(peer_id).to_string_lossy().into_owned();
 
-        let color_format = CStr::from_ptr((*frame).color_format)
-            .to_string_lossy()
-            .into_owned();
-
-        let video_frame = PyVideoFrame {
-            buffer: PyBytes::bound_from_ptr(py, (*frame).buffer, (*frame).buffer_size).into_py(py),
-            width: (*frame).width,
-            height: (*frame).height,
-            timestamp_us: (*frame).timestamp_us,
-            color_format: color_format.into_py(py),
-        };
+        let video_frame = video_frame_from_native(py, frame);
 
         let args = PyTuple::new_bound(py, &[peer_id.into_py(py), video_frame.into_py(py)]);
 
@@ -302,3 +762,333 @@ pub(crate) unsafe fn on_video_frame(
         }
     }
 }
+
+unsafe fn on_av_audio_data(
+    py: Python<'_>,
+    delegate_ctx: &DelegateContext,
+    av_renderer_id: u64,
+    peer_id: *const libc::c_char,
+    data: *const NativeAudioData,
+    timestamp_us: i64,
+    sample_offset: u64,
+) {
+    // Don't lock in the if statement otherwise the lock is held throughout the
+    // callback call.
+    let ready = delegate_ctx
+        .inner
+        .av_renderers
+        .lock()
+        .unwrap()
+        .get_mut(&av_renderer_id)
+        .and_then(|av_renderer| {
+            av_renderer.push_audio(data, timestamp_us, sample_offset);
+
+            let pending_ms = av_renderer
+                .pending_audio
+                .as_ref()
+                .map_or(0, |pending| pending.duration_ms());
+
+            if pending_ms >= AV_RENDERER_MAX_PENDING_AUDIO_MS {
+                av_renderer
+                    .pending_audio
+                    .take()
+                    .map(|pending| (av_renderer.callback.clone_ref(py), pending))
+            } else {
+                None
+            }
+        });
+
+    if let Some((callback, pending)) = ready {
+        let peer_id = CStr::from_ptr(peer_id).to_string_lossy().into_owned();
+
+        let audio_data = pending.into_audio_data(py);
+
+        let args = PyTuple::new_bound(
+            py,
+            &[peer_id.into_py(py), py.None(), audio_data.into_py(py)],
+        );
+
+        if let Err(error) = callback.call1(py, args) {
+            error.write_unraisable_bound(py, None);
+        }
+    }
+}
+
+unsafe fn on_av_video_frame(
+    py: Python<'_>,
+    delegate_ctx: &DelegateContext,
+    av_renderer_id: u64,
+    peer_id: *const libc::c_char,
+    frame: *const NativeVideoFrame,
+) {
+    // Don't lock in the if statement otherwise the lock is held throughout the
+    // callback call.
+    let ready = delegate_ctx
+        .inner
+        .av_renderers
+        .lock()
+        .unwrap()
+        .get_mut(&av_renderer_id)
+        .map(|av_renderer| {
+            (
+                av_renderer.callback.clone_ref(py),
+                av_renderer.pending_audio.take(),
+            )
+        });
+
+    if let Some((callback, pending)) = ready {
+        let peer_id = CStr::from_ptr(peer_id).to_string_lossy().into_owned();
+
+        let video_frame = video_frame_from_native(py, frame);
+
+        let audio_data =
+            pending.map_or(py.None(), |pending| pending.into_audio_data(py).into_py(py));
+
+        let args = PyTuple::new_bound(
+            py,
+            &[peer_id.into_py(py), video_frame.into_py(py), audio_data],
+        );
+
+        if let Err(error) = callback.call1(py, args) {
+            error.write_unraisable_bound(py, None);
+        }
+    }
+}
+
+unsafe fn audio_data_num_bytes(data: *const NativeAudioData) -> usize {
+    ((*data).bits_per_sample as usize * (*data).num_channels * (*data).num_audio_frames) / 8
+}
+
+unsafe fn audio_data_from_native(
+    py: Python<'_>,
+    data: *const NativeAudioData,
+    timestamp_us: i64,
+    sample_offset: u64,
+) -> PyAudioData {
+    let num_bytes = audio_data_num_bytes(data);
+
+    PyAudioData {
+        bits_per_sample: (*data).bits_per_sample,
+        sample_rate: (*data).sample_rate,
+        num_channels: (*data).num_channels,
+        num_audio_frames: (*data).num_audio_frames,
+        audio_frames: PyBytes::bound_from_ptr(py, (*data).audio_frames, num_bytes).into_py(py),
+        timestamp_us,
+        sample_offset,
+    }
+}
+
+unsafe fn video_frame_from_native(py: Python<'_>, frame: *const NativeVideoFrame) -> PyVideoFrame {
+    let color_format = CStr::from_ptr((*frame).color_format)
+        .to_string_lossy()
+        .into_owned();
+
+    PyVideoFrame {
+        buffer: PyBytes::bound_from_ptr(py, (*frame).buffer, (*frame).buffer_size).into_py(py),
+        width: (*frame).width,
+        height: (*frame).height,
+        timestamp_us: (*frame).timestamp_us,
+        color_format: color_format.into_py(py),
+    }
+}
+
+unsafe fn on_audio_data_level(
+    delegate_ctx: &DelegateContext,
+    renderer_id: u64,
+    peer_id: *const libc::c_char,
+    data: *const NativeAudioData,
+) {
+    // If PyCallClient is being released the audio delegate has already been
+    // cleaned up and we should not notify observers anymore.
+    if delegate_ctx
+        .inner
+        .delegates
+        .lock()
+        .unwrap()
+        .on_audio_data
+        .is_none()
+    {
+        return;
+    }
+
+    let level = {
+        let mut observers = delegate_ctx.inner.audio_level_observers.lock().unwrap();
+
+        if let Some(observer) = observers.get_mut(&renderer_id) {
+            if (*data).bits_per_sample == 16 {
+                let num_samples = (*data).num_channels * (*data).num_audio_frames;
+                let bytes = std::slice::from_raw_parts((*data).audio_frames, num_samples * 2);
+                let aligned = AlignedI16Data::new(bytes);
+                let samples = std::slice::from_raw_parts(aligned.as_ptr(), num_samples);
+
+                observer.meter.add_samples(samples);
+            }
+
+            if observer.last_report.elapsed() >= observer.interval {
+                let level = observer.meter.level();
+                observer.meter.reset();
+                observer.last_report = Instant::now();
+                Some(level)
+            } else {
+                None
+            }
+        } else {
+            return;
+        }
+    };
+
+    if let Some(level) = level {
+        Python::with_gil(|py| {
+            // Don't lock in the if statement otherwise the lock is held
+            // throughout the callback call.
+            let callback = delegate_ctx
+                .inner
+                .audio_level_observers
+                .lock()
+                .unwrap()
+                .get(&renderer_id)
+                .map(|observer| observer.callback.clone_ref(py));
+
+            if let Some(callback) = callback {
+                let peer_id = CStr::from_ptr(peer_id).to_string_lossy().into_owned();
+
+                let args = PyTuple::new_bound(py, &[peer_id.into_py(py), level.to_object(py)]);
+
+                if let Err(error) = callback.call1(py, args) {
+                    error.write_unraisable_bound(py, None);
+                }
+            }
+        });
+    }
+}
+
+// Whether there's someone in Python to deliver audio of the given renderer to.
+fn has_audio_renderer(delegate_ctx: &DelegateContext, renderer_id: u64) -> bool {
+    delegate_ctx
+        .inner
+        .av_renderer_ids
+        .lock()
+        .unwrap()
+        .contains_key(&renderer_id)
+        || delegate_ctx
+            .inner
+            .audio_renderers
+            .lock()
+            .unwrap()
+            .contains_key(&renderer_id)
+}
+
+unsafe fn on_video_frame_recording(
+    delegate_ctx: &DelegateContext,
+    renderer_id: u64,
+    frame: *const NativeVideoFrame,
+) -> bool {
+    // If PyCallClient is being released the video delegate has already been
+    // cleaned up and we should not touch the recorders anymore.
+    if delegate_ctx
+        .inner
+        .delegates
+        .lock()
+        .unwrap()
+        .on_video_frame
+        .is_none()
+    {
+        return false;
+    }
+
+    // Frames are only copied while holding the lock, the recorder writes them
+    // to disk in its own thread.
+    let (participant_id, video_source, result) = {
+        let mut recorders = delegate_ctx.inner.video_recorders.lock().unwrap();
+
+        let Some(recorder) = recorders.get_mut(&renderer_id) else {
+            return false;
+        };
+
+        let buffer = std::slice::from_raw_parts((*frame).buffer, (*frame).buffer_size);
+
+        let result = recorder.write_frame(
+            buffer,
+            (*frame).width,
+            (*frame).height,
+            (*frame).timestamp_us,
+        );
+
+        (
+            recorder.participant_id().to_string(),
+            recorder.video_source().to_string(),
+            result,
+        )
+    };
+
+    let error = match result {
+        Ok(()) => return true,
+        Err(RecordingError::FramesSkipped(message)) => message,
+        Err(RecordingError::Failed(message)) => {
+            // Stop recording, there's no point in trying with the next frames.
+            let recorder = delegate_ctx
+                .inner
+                .video_recorders
+                .lock()
+                .unwrap()
+                .remove(&renderer_id);
+
+            let Some(recorder) = recorder else {
+                report_recording_error(&delegate_ctx.inner, participant_id, video_source, message);
+                return true;
+            };
+
+            // Don't wait for the writer thread here, it might be stuck on a
+            // slow disk and we would block video delivery of all renderers.
+            let inner_weak = Arc::downgrade(&delegate_ctx.inner);
+            recorder.finish_in_background(move |result| {
+                if let Some(inner) = inner_weak.upgrade() {
+                    let error = result.err().map_or(message, |error| error.to_string());
+                    report_recording_error(&inner, participant_id, video_source, error);
+                }
+            });
+
+            return true;
+        }
+    };
+
+    report_recording_error(&delegate_ctx.inner, participant_id, video_source, error);
+
+    true
+}
+
+fn report_recording_error(
+    inner: &PyCallClientInner,
+    participant_id: String,
+    video_source: String,
+    error: String,
+) {
+    tracing::error!(
+        "local video recording of '{video_source}' of participant '{participant_id}': {error}"
+    );
+
+    Python::with_gil(|py| {
+        inner.emit_event(
+            py,
+            "on_local_video_recording_error",
+            (participant_id, video_source, error),
+        );
+    });
+}
+
+// Whether there's someone in Python to deliver frames of the given renderer
+// to.
+fn has_video_renderer(delegate_ctx: &DelegateContext, renderer_id: u64) -> bool {
+    delegate_ctx
+        .inner
+        .av_renderer_ids
+        .lock()
+        .unwrap()
+        .contains_key(&renderer_id)
+        || delegate_ctx
+            .inner
+            .video_renderers
+            .lock()
+            .unwrap()
+            .contains_key(&renderer_id)
+}
@@ -1,6 +1,7 @@
-use crate::util::dict::DictValue;
+use crate::{metrics::NetworkMetrics, util::dict::DictValue};
 
-use super::delegate::{DelegateContext, PyCallClientCompletion};
+use super::delegate::{CompletionResult, DelegateContext, PyCallClientCompletion};
+use super::reconnect::RendererRegistration;
 
 use serde::Deserialize;
 use serde_json::Value;
@@ -199,25 +200,30 @@ pub(crate) fn completion_args_from_event(
     completion: &PyCallClientCompletion,
     event: &Event,
 ) -> Option<Vec<DictValue>> {
+    // Channel completions don't call into Python.
+    if let PyCallClientCompletion::Channel(_) = completion {
+        return None;
+    }
+
     let object = event.data.0.as_object().expect("event should be an object");
     match event.action.as_str() {
         "request-completed" => {
             if let Some(request_success) = object.get("requestSuccess") {
                 let args = match completion {
-                    PyCallClientCompletion::UnaryFn(_) => {
-                        vec![DictValue(Value::Null)]
-                    }
                     PyCallClientCompletion::BinaryFn(_) => {
                         vec![DictValue(request_success.clone()), DictValue(Value::Null)]
                     }
+                    _ => {
+                        vec![DictValue(Value::Null)]
+                    }
                 };
                 Some(args)
             } else if let Some(request_error) = object.get("requestError") {
                 let args = request_error.get("msg").map(|msg| match completion {
-                    PyCallClientCompletion::UnaryFn(_) => vec![DictValue(msg.clone())],
                     PyCallClientCompletion::BinaryFn(_) => {
                         vec![DictValue(Value::Null), DictValue(msg.clone())]
                     }
+                    _ => vec![DictValue(msg.clone())],
                 });
                 Some(args.unwrap())
             } else {
@@ -234,6 +240,21 @@ pub(crate) fn completion_args_from_event(
     }
 }
 
+pub(crate) fn completion_result_from_event(event: &Event) -> CompletionResult {
+    let object = event.data.0.as_object().expect("event should be an object");
+    if let Some(request_success) = object.get("requestSuccess") {
+        Ok(request_success.clone())
+    } else if let Some(request_error) = object.get("requestError") {
+        Err(request_error
+            .get("msg")
+            .and_then(|msg| msg.as_str())
+            .unwrap_or("unknown error")
+            .to_string())
+    } else {
+        Ok(Value::Null)
+    }
+}
+
 pub(crate) fn update_inner_values(
     py: Python<'_>,
     delegate_ctx: &DelegateContext,
@@ -245,22 +266,89 @@ pub(crate) fn update_inner_values(
             let mut active_speaker = delegate_ctx.inner.active_speaker.lock().unwrap();
             *active_speaker = args.first().unwrap().to_object(py);
         }
+        "call-state-updated" => {
+            if let Some(state) = args.first().unwrap().0.as_str() {
+                if state == "joined" {
+                    delegate_ctx.inner.reconnector.lock().unwrap().connected();
+                }
+
+                *delegate_ctx.inner.call_state.lock().unwrap() = state.to_string();
+            }
+        }
         "inputs-updated" => {
             let mut inputs = delegate_ctx.inner.inputs.lock().unwrap();
             *inputs = args.first().unwrap().to_object(py);
         }
+        "live-stream-error" => {
+            let mut live_streams = delegate_ctx.inner.live_streams.lock().unwrap();
+            live_streams.error(&args[0].0, &args[1].0);
+        }
+        "live-stream-started" => {
+            let mut live_streams = delegate_ctx.inner.live_streams.lock().unwrap();
+            live_streams.started(&args.first().unwrap().0);
+        }
+        "live-stream-stopped" => {
+            let mut live_streams = delegate_ctx.inner.live_streams.lock().unwrap();
+            live_streams.stopped(&args.first().unwrap().0);
+        }
+        "live-stream-warning" => {
+            let mut live_streams = delegate_ctx.inner.live_streams.lock().unwrap();
+            live_streams.warning(&args[0].0, &args[1].0);
+        }
         "network-stats-updated" => {
+            let network = NetworkMetrics::from_stats(&args.first().unwrap().0);
+
+            delegate_ctx
+                .inner
+                .network_stats_history
+                .lock()
+                .unwrap()
+                .add(network.clone());
+
+            delegate_ctx.inner.metrics.update_network_stats(network);
+
             let mut network_stats = delegate_ctx.inner.network_stats.lock().unwrap();
             *network_stats = args.first().unwrap().to_object(py);
         }
         "participant-counts-updated" => {
+            delegate_ctx
+                .inner
+                .metrics
+                .update_participant_counts(&args.first().unwrap().0);
+
             let mut participant_counts = delegate_ctx.inner.participant_counts.lock().unwrap();
             *participant_counts = args.first().unwrap().to_object(py);
         }
+        "participant-left" => {
+            let participant_id = args.first().unwrap().0["id"].as_str().unwrap_or_default();
+
+            // Participant IDs are not reused, so the audio of the participant's
+            // renderers won't continue.
+            delegate_ctx
+                .inner
+                .remove_audio_sample_offsets(|registration| match registration {
+                    RendererRegistration::Audio {
+                        participant_id: p, ..
+                    } => p == participant_id,
+                    _ => false,
+                });
+        }
         "publishing-updated" => {
             let mut publishing = delegate_ctx.inner.publishing.lock().unwrap();
             *publishing = args.first().unwrap().to_object(py);
         }
+        "recording-error" => {
+            let mut recordings = delegate_ctx.inner.recordings.lock().unwrap();
+            recordings.error(&args[0].0, &args[1].0);
+        }
+        "recording-started" => {
+            let mut recordings = delegate_ctx.inner.recordings.lock().unwrap();
+            recordings.started(&args.first().unwrap().0);
+        }
+        "recording-stopped" => {
+            let mut recordings = delegate_ctx.inner.recordings.lock().unwrap();
+            recordings.stopped(&args.first().unwrap().0);
+        }
         "subscription-profiles-updated" => {
             let mut profiles = delegate_ctx.inner.subscription_profiles.lock().unwrap();
             *profiles = args.first().unwrap().to_object(py);
@@ -269,6 +357,14 @@ pub(crate) fn update_inner_values(
             let mut subscriptions = delegate_ctx.inner.subscriptions.lock().unwrap();
             *subscriptions = args.first().unwrap().to_object(py);
         }
+        "transcription-message" => {
+            delegate_ctx
+                .inner
+                .transcript
+                .lock()
+                .unwrap()
+                .add(&args.first().unwrap().0);
+        }
         _ => (),
     }
 }
@@ -11,9 +11,44 @@ use pyo3::types::PyTuple;
 /// should be created as a subclass of this class. Since event handlers are
 /// created as a subclass, there is no need implement all the handler methods.
 #[derive(Clone, Debug)]
-#[pyclass(name = "EventHandler", module = "daily", subclass)]
+#[pyclass(name = "EventHandler", module = "daily", subclass, weakref)]
 pub struct PyEventHandler;
 
+/// A reference to the event handler of a call client. A weak reference doesn't
+/// keep the event handler alive, so an event handler that holds its call client
+/// doesn't create a reference cycle.
+pub(crate) enum EventHandlerRef {
+    Strong(PyObject),
+    Weak(PyObject),
+}
+
+impl EventHandlerRef {
+    pub(crate) fn new(py: Python<'_>, event_handler: PyObject, weak: bool) -> PyResult<Self> {
+        if weak {
+            let weakref = py
+                .import_bound("weakref")?
+                .getattr("ref")?
+                .call1((event_handler,))?;
+            Ok(Self::Weak(weakref.unbind()))
+        } else {
+            Ok(Self::Strong(event_handler))
+        }
+    }
+
+    /// Returns the event handler, or `None` if it was weakly referenced and has
+    /// already been garbage collected.
+    pub(crate) fn get<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyAny>> {
+        match self {
+            Self::Strong(event_handler) => Some(event_handler.bind(py).clone()),
+            Self::Weak(weakref) => weakref
+                .bind(py)
+                .call0()
+                .ok()
+                .filter(|event_handler| !event_handler.is_none()),
+        }
+    }
+}
+
 #[pymethods]
 impl PyEventHandler {
     // Since this is a base class it might be that subclasses have constructor
@@ -34,6 +69,8 @@ impl PyEventHandler {
     }
 
     /// Event emitted when a custom app message is received from another participant.
+    /// Binary values in the message are received as `bytes`, see
+    /// :ref:`AppMessageBinary`.
     ///
     /// :param string message: Message received from a remote participant
     /// :param string sender: Participant ID that sent the message
@@ -157,6 +194,22 @@ impl PyEventHandler {
         Ok(())
     }
 
+    /// Event emitted when a local video recording skips frames or stops
+    /// because of an error. See
+    /// :func:`daily.CallClient.start_local_video_recording`.
+    ///
+    /// :param str participant_id: The ID of the participant being recorded
+    /// :param str video_source: The video source being recorded
+    /// :param str error: The error message
+    fn on_local_video_recording_error(
+        &self,
+        participant_id: PyObject,
+        video_source: PyObject,
+        error: PyObject,
+    ) -> PyResult<()> {
+        Ok(())
+    }
+
     /// Event emitted when the logging & telemetry backend updates the network
     /// statistics.
     ///
@@ -165,6 +218,16 @@ impl PyEventHandler {
         Ok(())
     }
 
+    /// Event emitted when the network quality of this client changes. The
+    /// network quality is evaluated with the thresholds set with
+    /// :func:`daily.CallClient.set_network_quality_thresholds`.
+    ///
+    /// :param str quality: See :ref:`NetworkQuality`
+    /// :param list reasons: The statistics that exceeded their thresholds (e.g. "packetLoss" or "roundTripTime")
+    fn on_network_quality_changed(&self, quality: PyObject, reasons: PyObject) -> PyResult<()> {
+        Ok(())
+    }
+
     /// Event emitted when the participant count changes.
     ///
     /// :param dict stats: See :ref:`ParticipantCounts`
@@ -196,6 +259,26 @@ impl PyEventHandler {
         Ok(())
     }
 
+    /// Event emitted when a Daily Prebuilt chat message is received (e.g. from
+    /// Prebuilt's chat or sent with
+    /// :func:`daily.CallClient.send_prebuilt_chat_message`). By default the
+    /// message is also delivered to :func:`on_app_message`, see
+    /// :func:`daily.CallClient.suppress_prebuilt_chat_app_messages`.
+    ///
+    /// :param string message: The chat message
+    /// :param string user_name: The user name of the sender, if any
+    /// :param string sender_id: Participant ID that sent the message
+    /// :param float timestamp: When the message was received, in seconds since the UNIX epoch
+    fn on_prebuilt_chat_message(
+        &self,
+        message: PyObject,
+        user_name: PyObject,
+        sender_id: PyObject,
+        timestamp: PyObject,
+    ) -> PyResult<()> {
+        Ok(())
+    }
+
     /// Event emitted when the publishing settings are updated, normally as a
     /// consequence of invocations to :func:`daily.CallClient.join`,
     /// :func:`daily.CallClient.update_publishing`.
@@ -205,6 +288,39 @@ impl PyEventHandler {
         Ok(())
     }
 
+    /// Event emitted when all the reconnection attempts of the reconnect
+    /// policy have failed. The client is not in the meeting anymore.
+    ///
+    /// :param int attempts: The number of reconnection attempts
+    /// :param string reason: Why the client got disconnected. See :ref:`ReconnectReason`
+    fn on_reconnect_failed(&self, attempts: PyObject, reason: PyObject) -> PyResult<()> {
+        Ok(())
+    }
+
+    /// Event emitted when the client has rejoined the meeting after being
+    /// disconnected.
+    ///
+    /// :param int attempts: The number of reconnection attempts that were needed
+    fn on_reconnected(&self, attempts: PyObject) -> PyResult<()> {
+        Ok(())
+    }
+
+    /// Event emitted when the client got disconnected and is about to try to
+    /// rejoin the meeting, as configured with
+    /// :func:`daily.CallClient.set_reconnect_policy`.
+    ///
+    /// :param int attempt: The reconnection attempt (starting at 1)
+    /// :param string reason: Why the client got disconnected. See :ref:`ReconnectReason`
+    /// :param float delay: Seconds until the meeting is rejoined
+    fn on_reconnecting(
+        &self,
+        attempt: PyObject,
+        reason: PyObject,
+        delay: PyObject,
+    ) -> PyResult<()> {
+        Ok(())
+    }
+
     /// Event emitted when a recording error occurs.
     ///
     /// :param string stream_id: The ID of the recording that generated the error
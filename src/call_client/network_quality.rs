@@ -0,0 +1,553 @@
+use serde_json::{Map, Value};
+
+use crate::metrics::NetworkMetrics;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum NetworkQuality {
+    Good,
+    Degraded,
+    Poor,
+}
+
+impl NetworkQuality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkQuality::Good => "good",
+            NetworkQuality::Degraded => "degraded",
+            NetworkQuality::Poor => "poor",
+        }
+    }
+}
+
+// Unless an exit threshold is given, values need to improve by this fraction
+// of a threshold to leave its quality. This avoids flapping between qualities
+// when a value sits on a threshold.
+const DEFAULT_HYSTERESIS: f64 = 0.2;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Threshold {
+    degraded: Option<f64>,
+    poor: Option<f64>,
+    // The values needed to leave a quality once it has been entered.
+    degraded_exit: Option<f64>,
+    poor_exit: Option<f64>,
+}
+
+impl Threshold {
+    fn new(degraded: f64, poor: f64) -> Self {
+        Self {
+            degraded: Some(degraded),
+            poor: Some(poor),
+            ..Default::default()
+        }
+    }
+
+    // For values where higher is worse (e.g. packet loss).
+    fn quality_above(&self, value: f64, current: NetworkQuality) -> NetworkQuality {
+        let reached = |quality, enter: Option<f64>, exit: Option<f64>| {
+            enter.is_some_and(|enter| {
+                let exit = exit.unwrap_or(enter * (1.0 - DEFAULT_HYSTERESIS));
+                value >= enter || (current >= quality && value > exit)
+            })
+        };
+
+        if reached(NetworkQuality::Poor, self.poor, self.poor_exit) {
+            NetworkQuality::Poor
+        } else if reached(NetworkQuality::Degraded, self.degraded, self.degraded_exit) {
+            NetworkQuality::Degraded
+        } else {
+            NetworkQuality::Good
+        }
+    }
+
+    // For values where lower is worse (e.g. bitrate).
+    fn quality_below(&self, value: f64, current: NetworkQuality) -> NetworkQuality {
+        let reached = |quality, enter: Option<f64>, exit: Option<f64>| {
+            enter.is_some_and(|enter| {
+                let exit = exit.unwrap_or(enter * (1.0 + DEFAULT_HYSTERESIS));
+                value <= enter || (current >= quality && value < exit)
+            })
+        };
+
+        if reached(NetworkQuality::Poor, self.poor, self.poor_exit) {
+            NetworkQuality::Poor
+        } else if reached(NetworkQuality::Degraded, self.degraded, self.degraded_exit) {
+            NetworkQuality::Degraded
+        } else {
+            NetworkQuality::Good
+        }
+    }
+
+    fn from_value(name: &str, value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Null => Ok(Self::default()),
+            Value::Object(object) => {
+                let level = |key: &str| match object.get(key) {
+                    None | Some(Value::Null) => Ok(None),
+                    Some(value) => value
+                        .as_f64()
+                        .map(Some)
+                        .ok_or_else(|| format!("'{name}.{key}' should be a number")),
+                };
+                Ok(Self {
+                    degraded: level("degraded")?,
+                    poor: level("poor")?,
+                    degraded_exit: level("degradedExit")?,
+                    poor_exit: level("poorExit")?,
+                })
+            }
+            _ => Err(format!(
+                "'{name}' should be a dict with 'degraded' and 'poor' values"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct NetworkQualityThresholds {
+    packet_loss: Threshold,
+    round_trip_time: Threshold,
+    send_bits_per_second: Threshold,
+    receive_bits_per_second: Threshold,
+    // Number of consecutive stats that need to agree before the quality
+    // changes. This avoids flapping between qualities.
+    samples: usize,
+}
+
+impl Default for NetworkQualityThresholds {
+    fn default() -> Self {
+        Self {
+            packet_loss: Threshold::new(0.05, 0.15),
+            round_trip_time: Threshold::new(0.3, 0.6),
+            send_bits_per_second: Threshold::default(),
+            receive_bits_per_second: Threshold::default(),
+            samples: 2,
+        }
+    }
+}
+
+impl NetworkQualityThresholds {
+    /// Updates the default thresholds with the given ones.
+    pub fn from_value(thresholds: &Map<String, Value>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        for (key, value) in thresholds {
+            match key.as_str() {
+                "packetLoss" => result.packet_loss = Threshold::from_value(key, value)?,
+                "roundTripTime" => result.round_trip_time = Threshold::from_value(key, value)?,
+                "sendBitsPerSecond" => {
+                    result.send_bits_per_second = Threshold::from_value(key, value)?
+                }
+                "receiveBitsPerSecond" => {
+                    result.receive_bits_per_second = Threshold::from_value(key, value)?
+                }
+                "samples" => {
+                    result.samples = value
+                        .as_u64()
+                        .filter(|samples| *samples > 0)
+                        .ok_or("'samples' should be a positive integer")?
+                        as usize
+                }
+                key => return Err(format!("unknown network quality threshold '{key}'")),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Evaluates the network quality of a call client from its network stats.
+pub(crate) struct NetworkQualityMonitor {
+    thresholds: NetworkQualityThresholds,
+    quality: NetworkQuality,
+    pending: Option<(NetworkQuality, usize)>,
+    // The quality of each value (packet loss, round trip time, send and
+    // receive bitrate) in the last stats that had it.
+    levels: [NetworkQuality; 4],
+}
+
+impl Default for NetworkQualityMonitor {
+    fn default() -> Self {
+        Self {
+            thresholds: NetworkQualityThresholds::default(),
+            quality: NetworkQuality::Good,
+            pending: None,
+            levels: [NetworkQuality::Good; 4],
+        }
+    }
+}
+
+impl NetworkQualityMonitor {
+    pub fn quality(&self) -> NetworkQuality {
+        self.quality
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: NetworkQualityThresholds) {
+        self.thresholds = thresholds;
+        self.pending = None;
+        self.levels = [NetworkQuality::Good; 4];
+    }
+
+    /// Returns the new quality and the reasons for it if the quality has
+    /// changed.
+    pub fn update(&mut self, metrics: &NetworkMetrics) -> Option<(NetworkQuality, Vec<String>)> {
+        let thresholds = &self.thresholds;
+        let levels = self.levels;
+
+        let packet_loss = match (metrics.receive_packet_loss, metrics.send_packet_loss) {
+            (Some(receive), Some(send)) => Some(receive.max(send)),
+            (receive, send) => receive.or(send),
+        };
+
+        let evaluations = [
+            (
+                "packetLoss",
+                packet_loss.map(|v| thresholds.packet_loss.quality_above(v, levels[0])),
+            ),
+            (
+                "roundTripTime",
+                metrics
+                    .round_trip_time
+                    .map(|v| thresholds.round_trip_time.quality_above(v, levels[1])),
+            ),
+            (
+                "sendBitsPerSecond",
+                metrics
+                    .send_bits_per_second
+                    .map(|v| thresholds.send_bits_per_second.quality_below(v, levels[2])),
+            ),
+            (
+                "receiveBitsPerSecond",
+                metrics.receive_bits_per_second.map(|v| {
+                    thresholds
+                        .receive_bits_per_second
+                        .quality_below(v, levels[3])
+                }),
+            ),
+        ];
+
+        for (level, (_, quality)) in self.levels.iter_mut().zip(&evaluations) {
+            if let Some(quality) = quality {
+                *level = *quality;
+            }
+        }
+
+        let quality = evaluations
+            .iter()
+            .filter_map(|(_, quality)| *quality)
+            .max()
+            .unwrap_or(NetworkQuality::Good);
+
+        if quality == self.quality {
+            self.pending = None;
+            return None;
+        }
+
+        let count = match self.pending {
+            Some((pending, count)) if pending == quality => count + 1,
+            _ => 1,
+        };
+
+        if count < self.thresholds.samples {
+            self.pending = Some((quality, count));
+            return None;
+        }
+
+        self.quality = quality;
+        self.pending = None;
+
+        let reasons = evaluations
+            .iter()
+            .filter(|(_, q)| q.is_some_and(|q| q > NetworkQuality::Good))
+            .map(|(reason, _)| reason.to_string())
+            .collect();
+
+        Some((quality, reasons))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn monitor(thresholds: Value) -> NetworkQualityMonitor {
+        let mut monitor = NetworkQualityMonitor::default();
+        monitor.set_thresholds(
+            NetworkQualityThresholds::from_value(thresholds.as_object().unwrap()).unwrap(),
+        );
+        monitor
+    }
+
+    fn packet_loss(value: f64) -> NetworkMetrics {
+        NetworkMetrics {
+            receive_packet_loss: Some(value),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn higher_is_worse_thresholds() {
+        let mut monitor = monitor(json!({"samples": 1}));
+
+        assert_eq!(monitor.update(&packet_loss(0.01)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.05)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+        assert_eq!(
+            monitor.update(&packet_loss(0.2)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+        assert_eq!(
+            monitor.update(&packet_loss(0.0)),
+            Some((NetworkQuality::Good, vec![]))
+        );
+
+        let metrics = NetworkMetrics {
+            round_trip_time: Some(0.7),
+            ..Default::default()
+        };
+        assert_eq!(
+            monitor.update(&metrics),
+            Some((NetworkQuality::Poor, vec!["roundTripTime".to_string()]))
+        );
+    }
+
+    #[test]
+    fn lower_is_worse_thresholds() {
+        let mut monitor = monitor(json!({
+            "samples": 1,
+            "sendBitsPerSecond": {"degraded": 500000, "poor": 100000},
+        }));
+
+        let send = |value| NetworkMetrics {
+            send_bits_per_second: Some(value),
+            ..Default::default()
+        };
+
+        assert_eq!(monitor.update(&send(1_000_000.0)), None);
+        assert_eq!(
+            monitor.update(&send(500_000.0)),
+            Some((
+                NetworkQuality::Degraded,
+                vec!["sendBitsPerSecond".to_string()]
+            ))
+        );
+        assert_eq!(
+            monitor.update(&send(50_000.0)),
+            Some((NetworkQuality::Poor, vec!["sendBitsPerSecond".to_string()]))
+        );
+        assert_eq!(
+            monitor.update(&send(700_000.0)),
+            Some((NetworkQuality::Good, vec![]))
+        );
+    }
+
+    #[test]
+    fn worst_quality_wins() {
+        let mut monitor = monitor(json!({"samples": 1}));
+
+        let metrics = NetworkMetrics {
+            receive_packet_loss: Some(0.01),
+            send_packet_loss: Some(0.2),
+            round_trip_time: Some(0.4),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            monitor.update(&metrics),
+            Some((
+                NetworkQuality::Poor,
+                vec!["packetLoss".to_string(), "roundTripTime".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn unset_thresholds_are_ignored() {
+        let mut monitor = monitor(json!({"samples": 1, "packetLoss": null}));
+
+        assert_eq!(monitor.update(&packet_loss(0.9)), None);
+        assert_eq!(monitor.update(&NetworkMetrics::default()), None);
+        assert_eq!(monitor.quality(), NetworkQuality::Good);
+    }
+
+    #[test]
+    fn quality_changes_after_consecutive_samples() {
+        let mut monitor = monitor(json!({"samples": 3}));
+
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.2)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+        assert_eq!(monitor.quality(), NetworkQuality::Poor);
+    }
+
+    #[test]
+    fn flapping_samples_restart_the_count() {
+        let mut monitor = monitor(json!({"samples": 2}));
+
+        // Back to the current quality.
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+        assert_eq!(monitor.update(&packet_loss(0.0)), None);
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+
+        // A different quality than the pending one.
+        assert_eq!(monitor.update(&packet_loss(0.1)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.1)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+    }
+
+    #[test]
+    fn setting_thresholds_restarts_the_count() {
+        let mut monitor = monitor(json!({"samples": 2}));
+
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+
+        monitor.set_thresholds(NetworkQualityThresholds::default());
+
+        assert_eq!(monitor.update(&packet_loss(0.2)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.2)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+    }
+
+    #[test]
+    fn values_on_a_threshold_dont_flap() {
+        let mut monitor = monitor(json!({"samples": 1}));
+
+        assert_eq!(
+            monitor.update(&packet_loss(0.05)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+
+        // Around the threshold, but not below the exit threshold (0.04).
+        for value in [0.049, 0.05, 0.045, 0.051, 0.041] {
+            assert_eq!(monitor.update(&packet_loss(value)), None);
+        }
+
+        assert_eq!(
+            monitor.update(&packet_loss(0.04)),
+            Some((NetworkQuality::Good, vec![]))
+        );
+        assert_eq!(monitor.update(&packet_loss(0.049)), None);
+    }
+
+    #[test]
+    fn leaving_poor_goes_through_degraded() {
+        let mut monitor = monitor(json!({"samples": 1}));
+
+        assert_eq!(
+            monitor.update(&packet_loss(0.15)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+        // Above the poor exit threshold (0.12).
+        assert_eq!(monitor.update(&packet_loss(0.13)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.1)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+        // Going back up needs the poor enter threshold.
+        assert_eq!(monitor.update(&packet_loss(0.14)), None);
+    }
+
+    #[test]
+    fn explicit_exit_thresholds() {
+        let mut monitor = monitor(json!({
+            "samples": 1,
+            "packetLoss": {"degraded": 0.1, "degradedExit": 0.02, "poor": 0.5, "poorExit": 0.5},
+        }));
+
+        assert_eq!(
+            monitor.update(&packet_loss(0.1)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+        assert_eq!(monitor.update(&packet_loss(0.03)), None);
+        assert_eq!(
+            monitor.update(&packet_loss(0.02)),
+            Some((NetworkQuality::Good, vec![]))
+        );
+
+        // No hysteresis for poor.
+        assert_eq!(
+            monitor.update(&packet_loss(0.5)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+        assert_eq!(
+            monitor.update(&packet_loss(0.49)),
+            Some((NetworkQuality::Degraded, vec!["packetLoss".to_string()]))
+        );
+    }
+
+    #[test]
+    fn lower_is_worse_values_on_a_threshold_dont_flap() {
+        let mut monitor = monitor(json!({
+            "samples": 1,
+            "receiveBitsPerSecond": {"degraded": 500000, "poor": 100000},
+        }));
+
+        let receive = |value| NetworkMetrics {
+            receive_bits_per_second: Some(value),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            monitor.update(&receive(500_000.0)),
+            Some((
+                NetworkQuality::Degraded,
+                vec!["receiveBitsPerSecond".to_string()]
+            ))
+        );
+        // Below the exit threshold (600000).
+        assert_eq!(monitor.update(&receive(550_000.0)), None);
+        assert_eq!(monitor.update(&receive(599_000.0)), None);
+        assert_eq!(
+            monitor.update(&receive(650_000.0)),
+            Some((NetworkQuality::Good, vec![]))
+        );
+    }
+
+    #[test]
+    fn hysteresis_is_per_value() {
+        let mut monitor = monitor(json!({"samples": 1}));
+
+        let metrics = |packet_loss, round_trip_time| NetworkMetrics {
+            receive_packet_loss: Some(packet_loss),
+            round_trip_time: Some(round_trip_time),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            monitor.update(&metrics(0.2, 0.1)),
+            Some((NetworkQuality::Poor, vec!["packetLoss".to_string()]))
+        );
+
+        // The round trip time never was poor, so it doesn't stay poor above
+        // its exit threshold.
+        assert_eq!(
+            monitor.update(&metrics(0.0, 0.55)),
+            Some((NetworkQuality::Degraded, vec!["roundTripTime".to_string()]))
+        );
+    }
+
+    #[test]
+    fn invalid_thresholds() {
+        let parse = |thresholds: Value| {
+            NetworkQualityThresholds::from_value(thresholds.as_object().unwrap()).map(|_| ())
+        };
+
+        assert!(parse(json!({"samples": 0})).is_err());
+        assert!(parse(json!({"packetLoss": 0.1})).is_err());
+        assert!(parse(json!({"packetLoss": {"poor": "high"}})).is_err());
+        assert!(parse(json!({"jitter": {"poor": 1.0}})).is_err());
+        assert!(parse(json!({"packetLoss": {"poorExit": "low"}})).is_err());
+        assert!(parse(json!({"roundTripTime": {"poor": 1.0}})).is_ok());
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::metrics::NetworkMetrics;
+use crate::util::time::system_time_secs;
+
+// Network stats are updated every few seconds, so this keeps at least the
+// last hour of stats.
+const NETWORK_STATS_HISTORY_MAX_SAMPLES: usize = 1800;
+
+struct NetworkStatsSample {
+    received: Instant,
+    timestamp: f64,
+    metrics: NetworkMetrics,
+}
+
+/// A bounded time series of the network stats received by a call client.
+#[derive(Default)]
+pub(crate) struct NetworkStatsHistory {
+    samples: VecDeque<NetworkStatsSample>,
+}
+
+#[derive(Default)]
+struct Aggregate {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: usize,
+}
+
+impl Aggregate {
+    fn add(&mut self, value: Option<f64>) {
+        if let Some(value) = value {
+            if self.count == 0 {
+                self.min = value;
+                self.max = value;
+            } else {
+                self.min = self.min.min(value);
+                self.max = self.max.max(value);
+            }
+            self.sum += value;
+            self.count += 1;
+        }
+    }
+
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        if self.count == 0 {
+            return py.None();
+        }
+
+        let aggregate = PyDict::new_bound(py);
+        let _ = aggregate.set_item("min", self.min);
+        let _ = aggregate.set_item("max", self.max);
+        let _ = aggregate.set_item("avg", self.sum / self.count as f64);
+        aggregate.into_py(py)
+    }
+}
+
+impl NetworkStatsHistory {
+    pub fn add(&mut self, metrics: NetworkMetrics) {
+        self.add_sample(metrics, Instant::now(), system_time_secs());
+    }
+
+    fn add_sample(&mut self, metrics: NetworkMetrics, received: Instant, timestamp: f64) {
+        if self.samples.len() == NETWORK_STATS_HISTORY_MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(NetworkStatsSample {
+            received,
+            timestamp,
+            metrics,
+        });
+    }
+
+    // The samples received within `window` before `now`, oldest first.
+    fn samples_since(&self, now: Instant, window: Duration) -> Vec<&NetworkStatsSample> {
+        self.samples
+            .iter()
+            .filter(|sample| now.saturating_duration_since(sample.received) <= window)
+            .collect()
+    }
+
+    pub fn to_object(&self, py: Python<'_>, window: Duration) -> PyObject {
+        let samples = self.samples_since(Instant::now(), window);
+
+        let mut receive_bitrate = Aggregate::default();
+        let mut send_bitrate = Aggregate::default();
+        let mut receive_packet_loss = Aggregate::default();
+        let mut send_packet_loss = Aggregate::default();
+        let mut round_trip_time = Aggregate::default();
+
+        let series = samples
+            .iter()
+            .map(|sample| {
+                let metrics = &sample.metrics;
+
+                receive_bitrate.add(metrics.receive_bits_per_second);
+                send_bitrate.add(metrics.send_bits_per_second);
+                receive_packet_loss.add(metrics.receive_packet_loss);
+                send_packet_loss.add(metrics.send_packet_loss);
+                round_trip_time.add(metrics.round_trip_time);
+
+                let values = PyDict::new_bound(py);
+                let _ = values.set_item("timestamp", sample.timestamp);
+                let _ = values.set_item("receiveBitsPerSecond", metrics.receive_bits_per_second);
+                let _ = values.set_item("sendBitsPerSecond", metrics.send_bits_per_second);
+                let _ = values.set_item("receivePacketLoss", metrics.receive_packet_loss);
+                let _ = values.set_item("sendPacketLoss", metrics.send_packet_loss);
+                let _ = values.set_item("roundTripTime", metrics.round_trip_time);
+                values.into_py(py)
+            })
+            .collect::<Vec<PyObject>>();
+
+        let history = PyDict::new_bound(py);
+        let _ = history.set_item("numSamples", samples.len());
+        let _ = history.set_item("receiveBitsPerSecond", receive_bitrate.to_object(py));
+        let _ = history.set_item("sendBitsPerSecond", send_bitrate.to_object(py));
+        let _ = history.set_item("receivePacketLoss", receive_packet_loss.to_object(py));
+        let _ = history.set_item("sendPacketLoss", send_packet_loss.to_object(py));
+        let _ = history.set_item("roundTripTime", round_trip_time.to_object(py));
+        let _ = history.set_item("samples", series);
+        history.into_py(py)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(
+        receive_bits_per_second: Option<f64>,
+        round_trip_time: Option<f64>,
+    ) -> NetworkMetrics {
+        NetworkMetrics {
+            receive_bits_per_second,
+            round_trip_time,
+            ..Default::default()
+        }
+    }
+
+    fn timestamps(samples: &[&NetworkStatsSample]) -> Vec<f64> {
+        samples.iter().map(|sample| sample.timestamp).collect()
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = NetworkStatsHistory::default();
+        let now = Instant::now();
+
+        for i in 0..NETWORK_STATS_HISTORY_MAX_SAMPLES + 10 {
+            history.add_sample(NetworkMetrics::default(), now, i as f64);
+        }
+
+        assert_eq!(history.samples.len(), NETWORK_STATS_HISTORY_MAX_SAMPLES);
+        // The oldest samples are dropped.
+        assert_eq!(history.samples.front().unwrap().timestamp, 10.0);
+        assert_eq!(
+            history.samples.back().unwrap().timestamp,
+            (NETWORK_STATS_HISTORY_MAX_SAMPLES + 9) as f64
+        );
+    }
+
+    #[test]
+    fn samples_within_window() {
+        let mut history = NetworkStatsHistory::default();
+        let start = Instant::now();
+
+        for i in 0..5 {
+            let received = start + Duration::from_secs(i * 2);
+            history.add_sample(NetworkMetrics::default(), received, i as f64);
+        }
+
+        let now = start + Duration::from_secs(8);
+
+        assert_eq!(
+            timestamps(&history.samples_since(now, Duration::from_secs(4))),
+            [2.0, 3.0, 4.0]
+        );
+        assert_eq!(
+            timestamps(&history.samples_since(now, Duration::from_secs(60))),
+            [0.0, 1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(
+            timestamps(&history.samples_since(now, Duration::ZERO)),
+            [4.0]
+        );
+        assert!(NetworkStatsHistory::default()
+            .samples_since(now, Duration::from_secs(60))
+            .is_empty());
+    }
+
+    #[test]
+    fn aggregate_ignores_missing_values() {
+        let mut aggregate = Aggregate::default();
+
+        for sample in [
+            metrics(Some(1000.0), None),
+            metrics(None, Some(0.1)),
+            metrics(Some(3000.0), Some(0.3)),
+            metrics(Some(2000.0), None),
+        ] {
+            aggregate.add(sample.receive_bits_per_second);
+        }
+
+        assert_eq!(aggregate.count, 3);
+        assert_eq!(aggregate.min, 1000.0);
+        assert_eq!(aggregate.max, 3000.0);
+        assert_eq!(aggregate.sum / aggregate.count as f64, 2000.0);
+    }
+
+    #[test]
+    fn aggregate_of_negative_values() {
+        let mut aggregate = Aggregate::default();
+
+        aggregate.add(Some(-1.0));
+        aggregate.add(Some(-3.0));
+
+        assert_eq!(aggregate.min, -3.0);
+        assert_eq!(aggregate.max, -1.0);
+    }
+
+    #[test]
+    fn empty_aggregate() {
+        let mut aggregate = Aggregate::default();
+
+        aggregate.add(None);
+
+        assert_eq!(aggregate.count, 0);
+    }
+}
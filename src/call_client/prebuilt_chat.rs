@@ -0,0 +1,33 @@
+use serde_json::Value;
+
+use crate::util::time::system_time_secs;
+
+/// A chat message sent from Daily Prebuilt (or with
+/// `send_prebuilt_chat_message()`). Prebuilt chat messages are app messages
+/// that look like `{"event": "chat-msg", "message": ..., "name": ..., "room":
+/// ...}`.
+pub(crate) struct PrebuiltChatMessage {
+    pub message: String,
+    pub user_name: Option<String>,
+    // Seconds since the UNIX epoch when the message was received.
+    pub timestamp: f64,
+}
+
+impl PrebuiltChatMessage {
+    pub fn from_value(message: &Value) -> Option<Self> {
+        let object = message.as_object()?;
+
+        if object.get("event")?.as_str()? != "chat-msg" {
+            return None;
+        }
+
+        Some(Self {
+            message: object.get("message")?.as_str()?.to_string(),
+            user_name: object
+                .get("name")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            timestamp: system_time_secs(),
+        })
+    }
+}
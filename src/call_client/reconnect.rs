@@ -0,0 +1,638 @@
+use std::{sync::Arc, time::Duration};
+
+use pyo3::prelude::*;
+use serde_json::{Map, Value};
+
+use super::delegate::PyCallClientInner;
+
+use crate::GLOBAL_CONTEXT;
+
+/// Why a call client got disconnected from the meeting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReconnectReason {
+    // The meeting was left without calling leave() and without an error.
+    Left,
+    // The meeting was left after an error event not listed below.
+    Error,
+    // The meeting was left after being ejected.
+    Ejected,
+    // The meeting was left because the meeting ended (e.g. the room expired
+    // or was deleted).
+    MeetingEnded,
+    // The meeting was left because the meeting token expired.
+    TokenExpired,
+}
+
+impl ReconnectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReconnectReason::Left => "left",
+            ReconnectReason::Error => "error",
+            ReconnectReason::Ejected => "ejected",
+            ReconnectReason::MeetingEnded => "meeting-ended",
+            ReconnectReason::TokenExpired => "token-expired",
+        }
+    }
+
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value.as_str() {
+            Some("left") => Ok(ReconnectReason::Left),
+            Some("error") => Ok(ReconnectReason::Error),
+            Some("ejected") => Ok(ReconnectReason::Ejected),
+            Some("meeting-ended") => Ok(ReconnectReason::MeetingEnded),
+            Some("token-expired") => Ok(ReconnectReason::TokenExpired),
+            _ => Err(format!("unknown reconnect reason {value}")),
+        }
+    }
+
+    /// The reason of a disconnection after the given error event. The error
+    /// type is given either in the event or in its message.
+    pub fn from_error_event(event: &Value) -> Self {
+        let error_type = event
+            .get("type")
+            .or_else(|| event.get("message").and_then(|message| message.get("type")))
+            .and_then(Value::as_str);
+
+        match error_type {
+            Some("ejected") => ReconnectReason::Ejected,
+            Some("exp-room" | "no-room" | "end-of-life") => ReconnectReason::MeetingEnded,
+            Some("exp-token") => ReconnectReason::TokenExpired,
+            _ => ReconnectReason::Error,
+        }
+    }
+
+    // Whether rejoining would fail or go against what ended the call.
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ReconnectReason::Ejected
+                | ReconnectReason::MeetingEnded
+                | ReconnectReason::TokenExpired
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ReconnectPolicy {
+    max_attempts: u32,
+    initial_delay: f64,
+    max_delay: f64,
+    backoff_multiplier: f64,
+    reasons: Vec<ReconnectReason>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: 1.0,
+            max_delay: 30.0,
+            backoff_multiplier: 2.0,
+            // Leaving without an error might also be intentional (e.g. the
+            // meeting was ended), so only errors that can be recovered from
+            // reconnect by default.
+            reasons: vec![ReconnectReason::Error],
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Updates the default policy with the given values.
+    pub fn from_value(policy: &Map<String, Value>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        let seconds = |key: &str, value: &Value| {
+            value
+                .as_f64()
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .ok_or_else(|| format!("'{key}' should be a non-negative number of seconds"))
+        };
+
+        for (key, value) in policy {
+            match key.as_str() {
+                "maxAttempts" => {
+                    result.max_attempts = value
+                        .as_u64()
+                        .and_then(|attempts| u32::try_from(attempts).ok())
+                        .ok_or("'maxAttempts' should be a non-negative integer")?
+                }
+                "initialDelay" => result.initial_delay = seconds(key, value)?,
+                "maxDelay" => result.max_delay = seconds(key, value)?,
+                "backoffMultiplier" => {
+                    result.backoff_multiplier = value
+                        .as_f64()
+                        .filter(|multiplier| multiplier.is_finite() && *multiplier >= 1.0)
+                        .ok_or("'backoffMultiplier' should be a number greater or equal than 1")?
+                }
+                "reasons" => {
+                    result.reasons = value
+                        .as_array()
+                        .ok_or("'reasons' should be a list")?
+                        .iter()
+                        .map(ReconnectReason::from_value)
+                        .collect::<Result<_, _>>()?
+                }
+                key => return Err(format!("unknown reconnect policy setting '{key}'")),
+            }
+        }
+
+        Ok(result)
+    }
+
+    // The delay before the given attempt (starting at 1).
+    fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_delay * self.backoff_multiplier.powi(exponent);
+        Duration::from_secs_f64(delay.min(self.max_delay))
+    }
+}
+
+/// The parameters of the last join, used to rejoin the meeting.
+#[derive(Clone, Debug)]
+pub(crate) struct JoinParams {
+    pub meeting_url: String,
+    pub meeting_token: Option<String>,
+    pub client_settings: Option<String>,
+}
+
+/// A native audio or video renderer, so it can be set again after rejoining.
+#[derive(Clone, Debug)]
+pub(crate) enum RendererRegistration {
+    Audio {
+        participant_id: String,
+        audio_source: String,
+    },
+    Video {
+        participant_id: String,
+        video_source: String,
+        color_format: String,
+    },
+}
+
+pub(crate) enum ReconnectAttempt {
+    // Rejoin after the given delay.
+    Scheduled { attempt: u32, delay: Duration },
+    // All the attempts have been used.
+    Failed { attempts: u32 },
+}
+
+/// Keeps track of the reconnection state of a call client.
+pub(crate) struct Reconnector {
+    policy: Option<ReconnectPolicy>,
+    join_params: Option<JoinParams>,
+    reason: ReconnectReason,
+    attempt: u32,
+    // Whether the meeting has been joined since the last join() or rejoin.
+    // Failed joins are not retried.
+    joined: bool,
+    // The reason given by the error events received since the meeting was
+    // joined, if any.
+    error: Option<ReconnectReason>,
+    // Whether an attempt is scheduled or in progress.
+    reconnecting: bool,
+    // Whether the meeting was left on purpose, in which case it's not
+    // rejoined.
+    stopped: bool,
+}
+
+impl Default for Reconnector {
+    fn default() -> Self {
+        Self {
+            policy: None,
+            join_params: None,
+            reason: ReconnectReason::Left,
+            attempt: 0,
+            joined: false,
+            error: None,
+            reconnecting: false,
+            stopped: true,
+        }
+    }
+}
+
+impl Reconnector {
+    pub fn set_policy(&mut self, policy: Option<ReconnectPolicy>) {
+        self.policy = policy;
+    }
+
+    /// The meeting is being joined on purpose.
+    pub fn joining(&mut self, join_params: JoinParams) {
+        self.join_params = Some(join_params);
+        self.attempt = 0;
+        self.joined = false;
+        self.reconnecting = false;
+        self.stopped = false;
+    }
+
+    /// The meeting has been joined.
+    pub fn connected(&mut self) {
+        self.joined = true;
+        self.error = None;
+    }
+
+    /// An error event was received. If the meeting is left afterwards, the
+    /// error is the reason. Terminal errors (e.g. being ejected) are not
+    /// replaced by later errors.
+    pub fn error_received(&mut self, reason: ReconnectReason) {
+        if self.joined && !self.error.is_some_and(|error| error.is_terminal()) {
+            self.error = Some(reason);
+        }
+    }
+
+    /// The meeting is being left on purpose.
+    pub fn leaving(&mut self) {
+        self.reconnecting = false;
+        self.stopped = true;
+    }
+
+    /// The call client got disconnected. Returns what to do next, if anything.
+    pub fn disconnected(&mut self) -> Option<ReconnectAttempt> {
+        if self.stopped || self.reconnecting || !self.joined {
+            return None;
+        }
+
+        let reason = self.error.unwrap_or(ReconnectReason::Left);
+
+        if !self.policy.as_ref()?.reasons.contains(&reason) {
+            return None;
+        }
+
+        self.reason = reason;
+        self.attempt = 0;
+        self.joined = false;
+        self.error = None;
+
+        Some(self.next_attempt())
+    }
+
+    /// The last rejoin attempt failed. Returns what to do next, if anything.
+    pub fn rejoin_failed(&mut self) -> Option<ReconnectAttempt> {
+        if self.stopped || !self.reconnecting {
+            return None;
+        }
+
+        Some(self.next_attempt())
+    }
+
+    /// The meeting has been rejoined. Returns the number of attempts needed,
+    /// unless the meeting was left in the meantime.
+    pub fn rejoined(&mut self) -> Option<u32> {
+        if self.stopped {
+            return None;
+        }
+
+        self.reconnecting = false;
+        Some(self.attempt)
+    }
+
+    /// Returns the parameters to rejoin with if we are still reconnecting.
+    pub fn rejoin_params(&self) -> Option<JoinParams> {
+        if self.stopped || !self.reconnecting {
+            None
+        } else {
+            self.join_params.clone()
+        }
+    }
+
+    pub fn reason(&self) -> ReconnectReason {
+        self.reason
+    }
+
+    fn next_attempt(&mut self) -> ReconnectAttempt {
+        let policy = self.policy.clone().unwrap_or_default();
+
+        if self.attempt >= policy.max_attempts {
+            self.reconnecting = false;
+            ReconnectAttempt::Failed {
+                attempts: self.attempt,
+            }
+        } else {
+            self.attempt += 1;
+            self.reconnecting = true;
+            ReconnectAttempt::Scheduled {
+                attempt: self.attempt,
+                delay: policy.delay(self.attempt),
+            }
+        }
+    }
+}
+
+/// Called when the call client gets disconnected from the meeting.
+pub(crate) fn on_disconnected(py: Python<'_>, inner: &Arc<PyCallClientInner>) {
+    let attempt = inner.reconnector.lock().unwrap().disconnected();
+    handle_attempt(py, inner, attempt);
+}
+
+/// Called when a rejoin started by the reconnect policy completes.
+pub(crate) fn on_rejoin_completed(py: Python<'_>, inner: &Arc<PyCallClientInner>, success: bool) {
+    if success {
+        let attempts = inner.reconnector.lock().unwrap().rejoined();
+
+        // The meeting was left while rejoining.
+        let Some(attempts) = attempts else {
+            return;
+        };
+
+        if let Some(call_client) = GLOBAL_CONTEXT.call_client(inner.call_client_id) {
+            call_client.restore_renderers();
+        }
+
+        inner.emit_event(py, "on_reconnected", (attempts,));
+    } else {
+        let attempt = inner.reconnector.lock().unwrap().rejoin_failed();
+        handle_attempt(py, inner, attempt);
+    }
+}
+
+fn handle_attempt(
+    py: Python<'_>,
+    inner: &Arc<PyCallClientInner>,
+    attempt: Option<ReconnectAttempt>,
+) {
+    let reason = inner.reconnector.lock().unwrap().reason();
+
+    match attempt {
+        Some(ReconnectAttempt::Scheduled { attempt, delay }) => {
+            inner.emit_event(
+                py,
+                "on_reconnecting",
+                (attempt, reason.as_str(), delay.as_secs_f64()),
+            );
+
+            let inner_weak = Arc::downgrade(inner);
+            inner.timer.schedule(delay, move || {
+                if let Some(inner) = inner_weak.upgrade() {
+                    rejoin(&inner);
+                }
+            });
+        }
+        Some(ReconnectAttempt::Failed { attempts }) => {
+            inner.emit_event(py, "on_reconnect_failed", (attempts, reason.as_str()));
+        }
+        None => (),
+    }
+}
+
+fn rejoin(inner: &PyCallClientInner) {
+    let join_params = inner.reconnector.lock().unwrap().rejoin_params();
+
+    // The meeting might have been left (or the client released) while waiting.
+    if let (Some(call_client), Some(join_params)) = (
+        GLOBAL_CONTEXT.call_client(inner.call_client_id),
+        join_params,
+    ) {
+        call_client.rejoin(&join_params);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn policy(policy: Value) -> ReconnectPolicy {
+        ReconnectPolicy::from_value(policy.as_object().unwrap()).unwrap()
+    }
+
+    fn join_params() -> JoinParams {
+        JoinParams {
+            meeting_url: "https://example.daily.co/room".to_string(),
+            meeting_token: None,
+            client_settings: None,
+        }
+    }
+
+    fn joined_reconnector(policy: ReconnectPolicy) -> Reconnector {
+        let mut reconnector = Reconnector::default();
+        reconnector.set_policy(Some(policy));
+        reconnector.joining(join_params());
+        reconnector.connected();
+        reconnector
+    }
+
+    fn scheduled(attempt: Option<ReconnectAttempt>) -> (u32, Duration) {
+        match attempt {
+            Some(ReconnectAttempt::Scheduled { attempt, delay }) => (attempt, delay),
+            Some(ReconnectAttempt::Failed { attempts }) => {
+                panic!("expected a scheduled attempt, failed after {attempts}")
+            }
+            None => panic!("expected a scheduled attempt"),
+        }
+    }
+
+    #[test]
+    fn delay_backs_off_up_to_the_maximum() {
+        let policy = policy(json!({
+            "initialDelay": 0.5,
+            "backoffMultiplier": 3.0,
+            "maxDelay": 10.0,
+        }));
+
+        assert_eq!(policy.delay(1), Duration::from_secs_f64(0.5));
+        assert_eq!(policy.delay(2), Duration::from_secs_f64(1.5));
+        assert_eq!(policy.delay(3), Duration::from_secs_f64(4.5));
+        assert_eq!(policy.delay(4), Duration::from_secs_f64(10.0));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs_f64(10.0));
+    }
+
+    #[test]
+    fn delay_without_backoff() {
+        let policy = policy(json!({"initialDelay": 2.0, "backoffMultiplier": 1.0}));
+
+        assert_eq!(policy.delay(1), Duration::from_secs(2));
+        assert_eq!(policy.delay(10), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn invalid_policies() {
+        let parse = |policy: Value| ReconnectPolicy::from_value(policy.as_object().unwrap());
+
+        assert!(parse(json!({"maxAttempts": -1})).is_err());
+        assert!(parse(json!({"initialDelay": -1.0})).is_err());
+        assert!(parse(json!({"backoffMultiplier": 0.5})).is_err());
+        assert!(parse(json!({"reasons": ["network"]})).is_err());
+        assert!(parse(json!({"retries": 3})).is_err());
+    }
+
+    #[test]
+    fn reconnects_until_rejoined() {
+        let mut reconnector =
+            joined_reconnector(policy(json!({"maxAttempts": 3, "reasons": ["left"]})));
+
+        assert_eq!(scheduled(reconnector.disconnected()).0, 1);
+        assert_eq!(reconnector.reason(), ReconnectReason::Left);
+        assert!(reconnector.rejoin_params().is_some());
+
+        // Disconnections while reconnecting don't start over.
+        assert!(reconnector.disconnected().is_none());
+
+        assert_eq!(scheduled(reconnector.rejoin_failed()).0, 2);
+        assert_eq!(reconnector.rejoined(), Some(2));
+        assert!(reconnector.rejoin_params().is_none());
+
+        // The next disconnection starts counting again.
+        reconnector.connected();
+        assert_eq!(scheduled(reconnector.disconnected()).0, 1);
+    }
+
+    #[test]
+    fn fails_after_max_attempts() {
+        let mut reconnector =
+            joined_reconnector(policy(json!({"maxAttempts": 2, "reasons": ["left"]})));
+
+        scheduled(reconnector.disconnected());
+        scheduled(reconnector.rejoin_failed());
+
+        assert!(matches!(
+            reconnector.rejoin_failed(),
+            Some(ReconnectAttempt::Failed { attempts: 2 })
+        ));
+        assert!(reconnector.rejoin_params().is_none());
+        assert!(reconnector.rejoin_failed().is_none());
+    }
+
+    #[test]
+    fn no_attempts_allowed() {
+        let mut reconnector =
+            joined_reconnector(policy(json!({"maxAttempts": 0, "reasons": ["left"]})));
+
+        assert!(matches!(
+            reconnector.disconnected(),
+            Some(ReconnectAttempt::Failed { attempts: 0 })
+        ));
+    }
+
+    #[test]
+    fn leaving_stops_reconnecting() {
+        let mut reconnector = joined_reconnector(ReconnectPolicy::default());
+
+        reconnector.error_received(ReconnectReason::Error);
+        scheduled(reconnector.disconnected());
+
+        reconnector.leaving();
+
+        assert!(reconnector.rejoin_params().is_none());
+        assert!(reconnector.rejoin_failed().is_none());
+        assert!(reconnector.rejoined().is_none());
+        assert!(reconnector.disconnected().is_none());
+    }
+
+    #[test]
+    fn requires_a_policy_and_a_join() {
+        let mut reconnector = Reconnector::default();
+        reconnector.joining(join_params());
+        reconnector.connected();
+        assert!(reconnector.disconnected().is_none());
+
+        // Failed joins are not retried.
+        let mut reconnector = Reconnector::default();
+        reconnector.set_policy(Some(ReconnectPolicy::default()));
+        reconnector.joining(join_params());
+        assert!(reconnector.disconnected().is_none());
+    }
+
+    #[test]
+    fn errors_are_the_reason_of_the_next_disconnection() {
+        let mut reconnector = joined_reconnector(policy(json!({"reasons": ["left", "error"]})));
+
+        reconnector.error_received(ReconnectReason::Error);
+        scheduled(reconnector.disconnected());
+        assert_eq!(reconnector.reason(), ReconnectReason::Error);
+
+        // The error is forgotten once rejoined.
+        reconnector.rejoined();
+        reconnector.connected();
+        scheduled(reconnector.disconnected());
+        assert_eq!(reconnector.reason(), ReconnectReason::Left);
+    }
+
+    #[test]
+    fn errors_before_joining_are_ignored() {
+        let mut reconnector = Reconnector::default();
+        reconnector.set_policy(Some(policy(json!({"reasons": ["left", "error"]}))));
+        reconnector.joining(join_params());
+        reconnector.error_received(ReconnectReason::Error);
+        reconnector.connected();
+
+        scheduled(reconnector.disconnected());
+        assert_eq!(reconnector.reason(), ReconnectReason::Left);
+    }
+
+    #[test]
+    fn only_policy_reasons_reconnect() {
+        let mut reconnector = joined_reconnector(policy(json!({"reasons": ["error"]})));
+        assert!(reconnector.disconnected().is_none());
+
+        let mut reconnector = joined_reconnector(policy(json!({"reasons": ["left"]})));
+        reconnector.error_received(ReconnectReason::Error);
+        assert!(reconnector.disconnected().is_none());
+
+        let mut reconnector =
+            joined_reconnector(policy(json!({"reasons": ["error", "token-expired"]})));
+        reconnector.error_received(ReconnectReason::TokenExpired);
+        scheduled(reconnector.disconnected());
+        assert_eq!(reconnector.reason(), ReconnectReason::TokenExpired);
+    }
+
+    #[test]
+    fn default_policy_ignores_intentional_and_terminal_leaves() {
+        let mut reconnector = joined_reconnector(ReconnectPolicy::default());
+        assert!(reconnector.disconnected().is_none());
+
+        for reason in [
+            ReconnectReason::Ejected,
+            ReconnectReason::MeetingEnded,
+            ReconnectReason::TokenExpired,
+        ] {
+            let mut reconnector = joined_reconnector(ReconnectPolicy::default());
+            reconnector.error_received(reason);
+            assert!(reconnector.disconnected().is_none());
+        }
+
+        let mut reconnector = joined_reconnector(ReconnectPolicy::default());
+        reconnector.error_received(ReconnectReason::Error);
+        scheduled(reconnector.disconnected());
+    }
+
+    #[test]
+    fn terminal_errors_are_not_replaced() {
+        let mut reconnector = joined_reconnector(ReconnectPolicy::default());
+
+        reconnector.error_received(ReconnectReason::Ejected);
+        reconnector.error_received(ReconnectReason::Error);
+        assert!(reconnector.disconnected().is_none());
+
+        let mut reconnector = joined_reconnector(policy(json!({"reasons": ["ejected"]})));
+        reconnector.error_received(ReconnectReason::Error);
+        reconnector.error_received(ReconnectReason::Ejected);
+        scheduled(reconnector.disconnected());
+        assert_eq!(reconnector.reason(), ReconnectReason::Ejected);
+    }
+
+    #[test]
+    fn error_event_reasons() {
+        let reason = |event: Value| ReconnectReason::from_error_event(&event);
+
+        assert_eq!(
+            reason(json!({"type": "ejected", "message": "ejected"})),
+            ReconnectReason::Ejected
+        );
+        assert_eq!(
+            reason(json!({"message": {"type": "exp-room", "msg": "room expired"}})),
+            ReconnectReason::MeetingEnded
+        );
+        assert_eq!(
+            reason(json!({"message": {"type": "exp-token"}})),
+            ReconnectReason::TokenExpired
+        );
+        assert_eq!(
+            reason(json!({"type": "connection-error"})),
+            ReconnectReason::Error
+        );
+        assert_eq!(
+            reason(json!({"message": "something went wrong"})),
+            ReconnectReason::Error
+        );
+    }
+}
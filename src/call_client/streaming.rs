@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::util::time::system_time_secs;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StreamingState {
+    Started,
+    Stopped,
+    Error,
+}
+
+impl StreamingState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            StreamingState::Started => "started",
+            StreamingState::Stopped => "stopped",
+            StreamingState::Error => "error",
+        }
+    }
+}
+
+struct Stream {
+    state: StreamingState,
+    // The status received when the stream started (e.g. layout, startedBy).
+    status: Map<String, Value>,
+    started_at: Option<f64>,
+    stopped_at: Option<f64>,
+    errors: Vec<Value>,
+    warnings: Vec<Value>,
+}
+
+impl Stream {
+    fn new(state: StreamingState) -> Self {
+        Self {
+            state,
+            status: Map::new(),
+            started_at: None,
+            stopped_at: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Keeps track of the state of the recordings or live streams of a call
+/// client, by stream ID.
+#[derive(Default)]
+pub(crate) struct StreamingTracker {
+    streams: HashMap<String, Stream>,
+}
+
+impl StreamingTracker {
+    /// A stream started, with the given status (see `RecordingStatus` or
+    /// `LiveStreamStatus`).
+    pub fn started(&mut self, status: &Value) {
+        let Some(status) = status.as_object() else {
+            return;
+        };
+        let Some(stream_id) = status.get("streamId").and_then(Value::as_str) else {
+            return;
+        };
+
+        // A stream ID might be reused (e.g. restarting a stream), start over.
+        let mut stream = Stream::new(StreamingState::Started);
+        stream.status = status.clone();
+        stream.started_at = Some(system_time_secs());
+
+        self.streams.insert(stream_id.to_string(), stream);
+    }
+
+    pub fn stopped(&mut self, stream_id: &Value) {
+        if let Some(stream) = self.stream(stream_id, StreamingState::Stopped) {
+            // Keep the error state if the stream stopped because of an error.
+            if stream.state == StreamingState::Started {
+                stream.state = StreamingState::Stopped;
+            }
+            stream.stopped_at.get_or_insert_with(system_time_secs);
+        }
+    }
+
+    pub fn error(&mut self, stream_id: &Value, message: &Value) {
+        if let Some(stream) = self.stream(stream_id, StreamingState::Error) {
+            let now = system_time_secs();
+            stream.state = StreamingState::Error;
+            stream.stopped_at.get_or_insert(now);
+            stream
+                .errors
+                .push(json!({ "message": message, "timestamp": now }));
+        }
+    }
+
+    pub fn warning(&mut self, stream_id: &Value, message: &Value) {
+        if let Some(stream) = self.stream(stream_id, StreamingState::Started) {
+            stream.warnings.push(json!({
+                "message": message,
+                "timestamp": system_time_secs(),
+            }));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.streams.clear();
+    }
+
+    /// Returns the state of all the streams, by stream ID.
+    pub fn to_value(&self) -> Value {
+        Value::Object(
+            self.streams
+                .iter()
+                .map(|(stream_id, stream)| {
+                    let mut object = stream.status.clone();
+                    object.insert("streamId".to_string(), stream_id.clone().into());
+                    object.insert("state".to_string(), stream.state.as_str().into());
+                    object.insert("startedAt".to_string(), stream.started_at.into());
+                    object.insert("stoppedAt".to_string(), stream.stopped_at.into());
+                    object.insert("errors".to_string(), stream.errors.clone().into());
+                    object.insert("warnings".to_string(), stream.warnings.clone().into());
+                    (stream_id.clone(), Value::Object(object))
+                })
+                .collect(),
+        )
+    }
+
+    // Returns the stream with the given ID. Streams we haven't seen start
+    // (e.g. started before joining) are added with the given state.
+    fn stream(&mut self, stream_id: &Value, state: StreamingState) -> Option<&mut Stream> {
+        let stream_id = stream_id.as_str()?;
+
+        Some(
+            self.streams
+                .entry(stream_id.to_string())
+                .or_insert_with(|| Stream::new(state)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(tracker: &StreamingTracker, stream_id: &str) -> Value {
+        tracker.to_value()[stream_id].clone()
+    }
+
+    fn messages(stream: &Value, key: &str) -> Vec<Value> {
+        stream[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                assert!(entry["timestamp"].as_f64().unwrap() > 0.0);
+                entry["message"].clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn started_keeps_the_status() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1", "layout": {"preset": "default"}}));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["streamId"], "s1");
+        assert_eq!(s1["state"], "started");
+        assert_eq!(s1["layout"], json!({"preset": "default"}));
+        assert!(s1["startedAt"].as_f64().unwrap() > 0.0);
+        assert!(s1["stoppedAt"].is_null());
+        assert_eq!(s1["errors"], json!([]));
+        assert_eq!(s1["warnings"], json!([]));
+    }
+
+    #[test]
+    fn invalid_statuses_are_ignored() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!("s1"));
+        tracker.started(&json!({"layout": {}}));
+        tracker.stopped(&Value::Null);
+        tracker.error(&json!(1), &json!("failed"));
+
+        assert_eq!(tracker.to_value(), json!({}));
+    }
+
+    #[test]
+    fn stopped() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1"}));
+        tracker.stopped(&json!("s1"));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "stopped");
+        let stopped_at = s1["stoppedAt"].as_f64().unwrap();
+        assert!(stopped_at >= s1["startedAt"].as_f64().unwrap());
+
+        // Stopping again keeps the first time.
+        tracker.stopped(&json!("s1"));
+        assert_eq!(stream(&tracker, "s1")["stoppedAt"], stopped_at);
+    }
+
+    #[test]
+    fn errors_stop_the_stream() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1"}));
+        tracker.error(&json!("s1"), &json!("failed"));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "error");
+        assert!(s1["stoppedAt"].is_f64());
+        assert_eq!(messages(&s1, "errors"), vec![json!("failed")]);
+
+        // Stopping after an error keeps the error state.
+        tracker.stopped(&json!("s1"));
+        tracker.error(&json!("s1"), &json!("failed again"));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "error");
+        assert_eq!(
+            messages(&s1, "errors"),
+            vec![json!("failed"), json!("failed again")]
+        );
+    }
+
+    #[test]
+    fn warnings_dont_change_the_state() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1"}));
+        tracker.warning(&json!("s1"), &json!("slow"));
+        tracker.warning(&json!("s1"), &json!("slower"));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "started");
+        assert!(s1["stoppedAt"].is_null());
+        assert_eq!(
+            messages(&s1, "warnings"),
+            vec![json!("slow"), json!("slower")]
+        );
+    }
+
+    #[test]
+    fn unknown_streams_are_added() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.warning(&json!("s1"), &json!("slow"));
+        tracker.stopped(&json!("s2"));
+        tracker.error(&json!("s3"), &json!("failed"));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "started");
+        assert!(s1["startedAt"].is_null());
+
+        assert_eq!(stream(&tracker, "s2")["state"], "stopped");
+        assert_eq!(stream(&tracker, "s3")["state"], "error");
+    }
+
+    #[test]
+    fn restarting_a_stream_starts_over() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1", "layout": {"preset": "default"}}));
+        tracker.error(&json!("s1"), &json!("failed"));
+        tracker.started(&json!({"streamId": "s1"}));
+
+        let s1 = stream(&tracker, "s1");
+        assert_eq!(s1["state"], "started");
+        assert!(s1["stoppedAt"].is_null());
+        assert!(s1.get("layout").is_none());
+        assert_eq!(s1["errors"], json!([]));
+    }
+
+    #[test]
+    fn clear() {
+        let mut tracker = StreamingTracker::default();
+
+        tracker.started(&json!({"streamId": "s1"}));
+        tracker.started(&json!({"streamId": "s2"}));
+        assert_eq!(tracker.to_value().as_object().unwrap().len(), 2);
+
+        tracker.clear();
+        assert_eq!(tracker.to_value(), json!({}));
+    }
+}
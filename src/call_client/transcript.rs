@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::util::time::{parse_rfc3339, system_time_secs};
+
+// An utterance is finished if the participant doesn't say anything for this
+// number of seconds.
+const UTTERANCE_MAX_GAP: f64 = 2.0;
+
+// Subtitles are displayed for at least this number of seconds.
+const MIN_CUE_DURATION: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TranscriptFormat {
+    Json,
+    Srt,
+    WebVtt,
+}
+
+impl TranscriptFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "json" => Some(TranscriptFormat::Json),
+            "srt" => Some(TranscriptFormat::Srt),
+            "vtt" => Some(TranscriptFormat::WebVtt),
+            _ => None,
+        }
+    }
+}
+
+/// A fragment of a transcription as received in a `transcription-message`
+/// event. Interim (non-final) fragments and end of speech are only known if
+/// the raw response is included.
+struct Fragment {
+    participant_id: String,
+    text: String,
+    start: f64,
+    end: f64,
+    is_final: bool,
+    speech_final: bool,
+}
+
+impl Fragment {
+    fn from_value(message: &Value) -> Option<Self> {
+        let object = message.as_object()?;
+
+        let participant_id = object.get("participantId")?.as_str()?.to_string();
+        let text = object.get("text")?.as_str()?.trim().to_string();
+
+        // Messages are timestamped when the transcription is received, which
+        // is the end of the fragment.
+        let end = object
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(parse_rfc3339)
+            .unwrap_or_else(system_time_secs);
+
+        let raw_response = object.get("rawResponse");
+        let raw_value = |key: &str| raw_response.and_then(|raw_response| raw_response.get(key));
+
+        let duration = raw_value("duration")
+            .and_then(Value::as_f64)
+            .filter(|duration| duration.is_finite() && *duration >= 0.0)
+            .unwrap_or(0.0);
+
+        Some(Self {
+            participant_id,
+            text,
+            start: end - duration,
+            end,
+            is_final: raw_value("is_final")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            speech_final: raw_value("speech_final")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Utterance {
+    participant_id: String,
+    // The text of the final fragments.
+    text: String,
+    // The text of the last interim fragment, replaced by the next fragment.
+    interim: String,
+    start: f64,
+    end: f64,
+}
+
+impl Utterance {
+    fn full_text(&self) -> String {
+        [self.text.as_str(), self.interim.as_str()]
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Groups transcription fragments into utterances per participant.
+#[derive(Default)]
+pub(crate) struct TranscriptAssembler {
+    // Finished utterances, in the order they finished.
+    utterances: Vec<Utterance>,
+    // The utterance currently being spoken by each participant.
+    open: HashMap<String, Utterance>,
+}
+
+impl TranscriptAssembler {
+    /// Adds the given `transcription-message` event data.
+    pub fn add(&mut self, message: &Value) {
+        let Some(fragment) = Fragment::from_value(message) else {
+            return;
+        };
+
+        let gap = self
+            .open
+            .get(&fragment.participant_id)
+            .map(|utterance| fragment.start - utterance.end);
+
+        if gap.is_some_and(|gap| gap > UTTERANCE_MAX_GAP) {
+            self.finish(&fragment.participant_id);
+        }
+
+        let utterance = self
+            .open
+            .entry(fragment.participant_id.clone())
+            .or_insert_with(|| Utterance {
+                participant_id: fragment.participant_id.clone(),
+                text: String::new(),
+                interim: String::new(),
+                start: fragment.start,
+                end: fragment.end,
+            });
+
+        utterance.start = utterance.start.min(fragment.start);
+        utterance.end = utterance.end.max(fragment.end);
+
+        if fragment.is_final {
+            if !fragment.text.is_empty() {
+                if !utterance.text.is_empty() {
+                    utterance.text.push(' ');
+                }
+                utterance.text.push_str(&fragment.text);
+            }
+            utterance.interim.clear();
+        } else {
+            utterance.interim = fragment.text;
+        }
+
+        if fragment.speech_final {
+            self.finish(&fragment.participant_id);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.utterances.clear();
+        self.open.clear();
+    }
+
+    /// Returns the transcript as a list of utterances.
+    pub fn to_value(&self) -> Value {
+        let utterances = self.all();
+        let origin = utterances.first().map_or(0.0, |utterance| utterance.start);
+
+        Value::Array(
+            utterances
+                .iter()
+                .map(|utterance| {
+                    json!({
+                        "participantId": utterance.participant_id,
+                        "text": utterance.full_text(),
+                        "start": utterance.start - origin,
+                        "end": utterance.end - origin,
+                        "startTime": utterance.start,
+                        "endTime": utterance.end,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Exports the transcript in the given format.
+    pub fn export(&self, format: TranscriptFormat) -> String {
+        match format {
+            TranscriptFormat::Json => serde_json::to_string_pretty(&self.to_value()).unwrap(),
+            TranscriptFormat::Srt => self.to_subtitles(false),
+            TranscriptFormat::WebVtt => self.to_subtitles(true),
+        }
+    }
+
+    fn finish(&mut self, participant_id: &str) {
+        if let Some(utterance) = self.open.remove(participant_id) {
+            if !utterance.full_text().is_empty() {
+                self.utterances.push(utterance);
+            }
+        }
+    }
+
+    // All the utterances, including the ones still being spoken, sorted by
+    // start time.
+    fn all(&self) -> Vec<Utterance> {
+        let mut utterances: Vec<Utterance> = self
+            .utterances
+            .iter()
+            .chain(self.open.values())
+            .filter(|utterance| !utterance.full_text().is_empty())
+            .cloned()
+            .collect();
+
+        utterances.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        utterances
+    }
+
+    fn to_subtitles(&self, webvtt: bool) -> String {
+        let utterances = self.all();
+        let origin = utterances.first().map_or(0.0, |utterance| utterance.start);
+
+        let mut result = String::new();
+
+        if webvtt {
+            result.push_str("WEBVTT\n\n");
+        }
+
+        for (index, utterance) in utterances.iter().enumerate() {
+            let start = utterance.start - origin;
+            let end = (utterance.end - origin).max(start + MIN_CUE_DURATION);
+
+            if webvtt {
+                result.push_str(&format!(
+                    "{} --> {}\n<v {}>{}\n\n",
+                    format_cue_time(start, '.'),
+                    format_cue_time(end, '.'),
+                    escape_webvtt(&utterance.participant_id),
+                    escape_webvtt(&utterance.full_text())
+                ));
+            } else {
+                result.push_str(&format!(
+                    "{}\n{} --> {}\n{}: {}\n\n",
+                    index + 1,
+                    format_cue_time(start, ','),
+                    format_cue_time(end, ','),
+                    utterance.participant_id,
+                    utterance.full_text()
+                ));
+            }
+        }
+
+        result
+    }
+}
+
+// Formats the given seconds as HH:MM:SS followed by the milliseconds.
+fn format_cue_time(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
+
+fn escape_webvtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A transcription message received `end` seconds after 10:20:00 (UTC).
+    fn message(participant_id: &str, text: &str, end: f64, duration: f64) -> Value {
+        json!({
+            "participantId": participant_id,
+            "text": text,
+            "timestamp": format!("2024-05-01T10:20:{end:06.3}Z"),
+            "rawResponse": {
+                "duration": duration,
+                "is_final": true,
+                "speech_final": false,
+            },
+        })
+    }
+
+    fn interim(participant_id: &str, text: &str, end: f64, duration: f64) -> Value {
+        let mut message = message(participant_id, text, end, duration);
+        message["rawResponse"]["is_final"] = false.into();
+        message
+    }
+
+    fn speech_final(participant_id: &str, text: &str, end: f64, duration: f64) -> Value {
+        let mut message = message(participant_id, text, end, duration);
+        message["rawResponse"]["speech_final"] = true.into();
+        message
+    }
+
+    fn texts(assembler: &TranscriptAssembler) -> Vec<String> {
+        assembler
+            .to_value()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|utterance| utterance["text"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn interim_fragments_are_replaced() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&interim("a", "hel", 10.5, 0.5));
+        assert_eq!(texts(&assembler), ["hel"]);
+
+        assembler.add(&interim("a", "hello wor", 11.0, 1.0));
+        assert_eq!(texts(&assembler), ["hello wor"]);
+
+        assembler.add(&message("a", "hello world", 11.5, 1.5));
+        assert_eq!(texts(&assembler), ["hello world"]);
+
+        assembler.add(&interim("a", "how", 12.0, 0.5));
+        assert_eq!(texts(&assembler), ["hello world how"]);
+
+        assembler.add(&message("a", "how are you", 13.0, 1.5));
+        assert_eq!(texts(&assembler), ["hello world how are you"]);
+    }
+
+    #[test]
+    fn gaps_split_utterances() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&message("a", "one", 11.0, 1.0));
+        // Exactly the maximum gap.
+        assembler.add(&message("a", "two", 14.0, 1.0));
+        assert_eq!(texts(&assembler), ["one two"]);
+
+        assembler.add(&message("a", "three", 17.5, 1.0));
+        assert_eq!(texts(&assembler), ["one two", "three"]);
+    }
+
+    #[test]
+    fn speech_final_finishes_utterances() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&speech_final("a", "one", 11.0, 1.0));
+        assembler.add(&message("a", "two", 11.5, 0.5));
+        assert_eq!(texts(&assembler), ["one", "two"]);
+    }
+
+    #[test]
+    fn participants_have_separate_utterances() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&message("a", "hello", 11.0, 1.0));
+        assembler.add(&message("b", "hi", 11.5, 1.0));
+        assembler.add(&message("a", "there", 12.0, 1.0));
+        assert_eq!(texts(&assembler), ["hello there", "hi"]);
+    }
+
+    #[test]
+    fn invalid_messages_are_ignored() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&json!({"text": "hello"}));
+        assembler.add(&json!("hello"));
+        assert!(texts(&assembler).is_empty());
+    }
+
+    #[test]
+    fn srt() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&message("a", "hello", 11.5, 1.5));
+        // Shorter than the minimum cue duration.
+        assembler.add(&message("b", "hi", 15.5, 0.5));
+
+        assert_eq!(
+            assembler.export(TranscriptFormat::Srt),
+            "1\n00:00:00,000 --> 00:00:01,500\na: hello\n\n\
+             2\n00:00:05,000 --> 00:00:06,000\nb: hi\n\n"
+        );
+    }
+
+    #[test]
+    fn webvtt() {
+        let mut assembler = TranscriptAssembler::default();
+
+        assembler.add(&message("a", "hello", 11.5, 1.5));
+        assembler.add(&message("b", "<b> & </b>", 15.5, 0.5));
+
+        assert_eq!(
+            assembler.export(TranscriptFormat::WebVtt),
+            "WEBVTT\n\n\
+             00:00:00.000 --> 00:00:01.500\n<v a>hello\n\n\
+             00:00:05.000 --> 00:00:06.000\n<v b>&lt;b&gt; &amp; &lt;/b&gt;\n\n"
+        );
+    }
+
+    #[test]
+    fn cue_times() {
+        assert_eq!(format_cue_time(0.0, ','), "00:00:00,000");
+        assert_eq!(format_cue_time(3723.4567, '.'), "01:02:03.457");
+        assert_eq!(format_cue_time(-1.0, '.'), "00:00:00.000");
+    }
+}
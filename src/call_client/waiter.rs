@@ -0,0 +1,351 @@
+use std::{
+    collections::HashMap,
+    sync::{mpsc, Mutex},
+};
+
+use pyo3::prelude::*;
+use serde_json::Value;
+
+use crate::util::dict::DictValue;
+
+pub(crate) const CALL_STATES: [&str; 5] = ["initialized", "joining", "joined", "leaving", "left"];
+
+pub(crate) const TRACK_KINDS: [&str; 4] = ["camera", "microphone", "screenVideo", "screenAudio"];
+
+pub(crate) const TRACK_STATES: [&str; 6] = [
+    "receivable",
+    "playable",
+    "loading",
+    "interrupted",
+    "blocked",
+    "off",
+];
+
+#[derive(Clone)]
+pub(crate) enum ParticipantMatcher {
+    Id(String),
+    Predicate(PyObject),
+}
+
+impl ParticipantMatcher {
+    fn matches(&self, participant: &Value) -> bool {
+        match self {
+            ParticipantMatcher::Id(participant_id) => is_participant(participant, participant_id),
+            // Only predicates need the GIL, which is already held by our
+            // callers, so this doesn't block.
+            ParticipantMatcher::Predicate(predicate) => Python::with_gil(|py| {
+                let participant = DictValue(participant.clone()).to_object(py);
+
+                match predicate
+                    .call1(py, (participant,))
+                    .and_then(|result| result.is_truthy(py))
+                {
+                    Ok(matches) => matches,
+                    Err(error) => {
+                        error.write_unraisable_bound(py, None);
+                        false
+                    }
+                }
+            }),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum WaitCondition {
+    CallState(String),
+    Participant(ParticipantMatcher),
+    Track {
+        participant_id: String,
+        kind: String,
+        state: String,
+    },
+}
+
+impl WaitCondition {
+    /// Returns the result for the waiter if the given event satisfies the
+    /// condition.
+    fn check_event(&self, action: &str, value: &Value) -> Option<Value> {
+        match (self, action) {
+            (WaitCondition::CallState(state), "call-state-updated") => {
+                (value.as_str() == Some(state)).then(|| value.clone())
+            }
+            (WaitCondition::Participant(matcher), "participant-joined" | "participant-updated") => {
+                matcher.matches(value).then(|| value.clone())
+            }
+            (
+                WaitCondition::Track {
+                    participant_id,
+                    kind,
+                    state,
+                },
+                "participant-joined" | "participant-updated",
+            ) => track_in_state(value, participant_id, kind, state),
+            _ => None,
+        }
+    }
+
+    /// Returns the result for the waiter if the current call state or
+    /// participants already satisfy the condition.
+    pub fn check_current(&self, call_state: &str, participants: &Value) -> Option<Value> {
+        let mut participants = participants
+            .as_object()
+            .into_iter()
+            .flat_map(|participants| participants.values());
+
+        match self {
+            WaitCondition::CallState(state) => {
+                (call_state == state).then(|| Value::String(call_state.to_string()))
+            }
+            WaitCondition::Participant(matcher) => participants
+                .find(|participant| matcher.matches(participant))
+                .cloned(),
+            WaitCondition::Track {
+                participant_id,
+                kind,
+                state,
+            } => participants
+                .find_map(|participant| track_in_state(participant, participant_id, kind, state)),
+        }
+    }
+
+    /// Whether the participants are needed to check the current state.
+    pub fn needs_participants(&self) -> bool {
+        !matches!(self, WaitCondition::CallState(_))
+    }
+}
+
+pub(crate) struct Waiter {
+    pub condition: WaitCondition,
+    pub sender: mpsc::Sender<Value>,
+}
+
+/// Wakes up the waiters whose condition is satisfied by the given event.
+pub(crate) fn notify_waiters(waiters: &Mutex<HashMap<u64, Waiter>>, action: &str, value: &Value) {
+    // Don't hold the lock while checking conditions, predicates call into
+    // Python.
+    let conditions: Vec<(u64, WaitCondition)> = waiters
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(waiter_id, waiter)| (*waiter_id, waiter.condition.clone()))
+        .collect();
+
+    for (waiter_id, condition) in conditions {
+        if let Some(result) = condition.check_event(action, value) {
+            // The waiter might have timed out in the meantime.
+            if let Some(waiter) = waiters.lock().unwrap().remove(&waiter_id) {
+                let _ = waiter.sender.send(result);
+            }
+        }
+    }
+}
+
+fn is_participant(participant: &Value, participant_id: &str) -> bool {
+    if participant.get("id").and_then(Value::as_str) == Some(participant_id) {
+        return true;
+    }
+
+    participant_id == "local"
+        && participant
+            .pointer("/info/isLocal")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+}
+
+fn track_in_state(
+    participant: &Value,
+    participant_id: &str,
+    kind: &str,
+    state: &str,
+) -> Option<Value> {
+    if !is_participant(participant, participant_id) {
+        return None;
+    }
+
+    let track = participant.get("media")?.get(kind)?;
+
+    (track.get("state")?.as_str() == Some(state)).then(|| track.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn participant(id: &str, is_local: bool, camera_state: &str) -> Value {
+        json!({
+            "id": id,
+            "info": {"isLocal": is_local},
+            "media": {"camera": {"state": camera_state}},
+        })
+    }
+
+    fn participants() -> Value {
+        json!({
+            "local": participant("local-id", true, "off"),
+            "remote-id": participant("remote-id", false, "playable"),
+        })
+    }
+
+    fn track(participant_id: &str, state: &str) -> WaitCondition {
+        WaitCondition::Track {
+            participant_id: participant_id.to_string(),
+            kind: "camera".to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    fn participant_id(participant_id: &str) -> WaitCondition {
+        WaitCondition::Participant(ParticipantMatcher::Id(participant_id.to_string()))
+    }
+
+    #[test]
+    fn call_state_condition() {
+        let condition = WaitCondition::CallState("joined".to_string());
+
+        assert_eq!(
+            condition.check_current("joined", &Value::Null),
+            Some(json!("joined"))
+        );
+        assert_eq!(condition.check_current("joining", &Value::Null), None);
+
+        assert_eq!(
+            condition.check_event("call-state-updated", &json!("joined")),
+            Some(json!("joined"))
+        );
+        assert_eq!(
+            condition.check_event("call-state-updated", &json!("left")),
+            None
+        );
+        assert_eq!(
+            condition.check_event("participant-joined", &json!("joined")),
+            None
+        );
+
+        assert!(!condition.needs_participants());
+    }
+
+    #[test]
+    fn participant_condition() {
+        let remote = participant("remote-id", false, "playable");
+
+        assert_eq!(
+            participant_id("remote-id").check_current("joined", &participants()),
+            Some(remote.clone())
+        );
+        assert_eq!(
+            participant_id("unknown").check_current("joined", &participants()),
+            None
+        );
+
+        // The local participant can be matched with "local" or its ID.
+        let local = participant("local-id", true, "off");
+        assert_eq!(
+            participant_id("local").check_current("joined", &participants()),
+            Some(local.clone())
+        );
+        assert_eq!(
+            participant_id("local-id").check_current("joined", &participants()),
+            Some(local)
+        );
+        assert_eq!(
+            participant_id("local").check_event("participant-joined", &remote),
+            None
+        );
+
+        let condition = participant_id("remote-id");
+        assert_eq!(
+            condition.check_event("participant-joined", &remote),
+            Some(remote.clone())
+        );
+        assert_eq!(
+            condition.check_event("participant-updated", &remote),
+            Some(remote.clone())
+        );
+        assert_eq!(condition.check_event("participant-left", &remote), None);
+
+        assert!(condition.needs_participants());
+    }
+
+    #[test]
+    fn track_condition() {
+        assert_eq!(
+            track("remote-id", "playable").check_current("joined", &participants()),
+            Some(json!({"state": "playable"}))
+        );
+        assert_eq!(
+            track("local", "off").check_current("joined", &participants()),
+            Some(json!({"state": "off"}))
+        );
+        assert_eq!(
+            track("remote-id", "off").check_current("joined", &participants()),
+            None
+        );
+        assert_eq!(
+            track("remote-id", "playable").check_current("joined", &Value::Null),
+            None
+        );
+
+        let condition = track("remote-id", "playable");
+        assert_eq!(
+            condition.check_event(
+                "participant-updated",
+                &participant("remote-id", false, "playable")
+            ),
+            Some(json!({"state": "playable"}))
+        );
+        assert_eq!(
+            condition.check_event(
+                "participant-updated",
+                &participant("remote-id", false, "loading")
+            ),
+            None
+        );
+        assert_eq!(
+            condition.check_event(
+                "participant-updated",
+                &participant("other-id", false, "playable")
+            ),
+            None
+        );
+        // Participants without the track.
+        assert_eq!(
+            condition.check_event("participant-joined", &json!({"id": "remote-id"})),
+            None
+        );
+    }
+
+    #[test]
+    fn notify_waiters_removes_satisfied_waiters() {
+        let waiters = Mutex::new(HashMap::new());
+
+        let (joined_sender, joined_receiver) = mpsc::channel();
+        let (left_sender, left_receiver) = mpsc::channel();
+
+        waiters.lock().unwrap().insert(
+            1,
+            Waiter {
+                condition: WaitCondition::CallState("joined".to_string()),
+                sender: joined_sender,
+            },
+        );
+        waiters.lock().unwrap().insert(
+            2,
+            Waiter {
+                condition: WaitCondition::CallState("left".to_string()),
+                sender: left_sender,
+            },
+        );
+
+        notify_waiters(&waiters, "call-state-updated", &json!("joined"));
+
+        assert_eq!(joined_receiver.try_recv(), Ok(json!("joined")));
+        assert!(left_receiver.try_recv().is_err());
+        assert_eq!(
+            waiters.lock().unwrap().keys().copied().collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+}
@@ -1,7 +1,14 @@
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::process;
+use std::ptr;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
 
+use crate::call_client::{delegate::CompletionResult, CallClientHandle, EXIT_LEAVE_TIMEOUT};
+use crate::metrics::{render_metrics, server::MetricsServer};
 use crate::PyNativeVad;
 use crate::PyVirtualCameraDevice;
 use crate::PyVirtualMicrophoneDevice;
@@ -18,7 +25,7 @@ use daily_core::prelude::{
     daily_core_context_create_audio_device_module, daily_core_context_create_device_manager,
     daily_core_context_create_vad, daily_core_context_create_virtual_camera_device,
     daily_core_context_create_virtual_microphone_device,
-    daily_core_context_create_virtual_speaker_device,
+    daily_core_context_create_virtual_speaker_device, daily_core_context_destroy,
     daily_core_context_device_manager_enumerated_devices,
     daily_core_context_device_manager_get_user_media,
     daily_core_context_get_selected_microphone_device, daily_core_context_select_speaker_device,
@@ -27,41 +34,427 @@ use daily_core::prelude::{
 
 use pyo3::exceptions;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 lazy_static! {
     pub(crate) static ref GLOBAL_CONTEXT: DailyContext = DailyContext::new();
 }
 
+pub(crate) enum VirtualDevice {
+    Camera(Py<PyVirtualCameraDevice>),
+    Microphone(Py<PyVirtualMicrophoneDevice>),
+    Speaker(Py<PyVirtualSpeakerDevice>),
+}
+
+impl VirtualDevice {
+    // Closes the native device. This fails instead of panicking if the device
+    // is being used (e.g. another thread is writing frames with the GIL
+    // released).
+    fn close(&self, py: Python<'_>) -> PyResult<()> {
+        match self {
+            VirtualDevice::Camera(camera) => camera.try_borrow_mut(py)?.close_device(),
+            VirtualDevice::Microphone(microphone) => {
+                microphone.try_borrow_mut(py)?.close_device(py)
+            }
+            VirtualDevice::Speaker(speaker) => speaker.try_borrow_mut(py)?.close_device(py),
+        }
+        Ok(())
+    }
+
+    // Drops the native device without releasing it. In a forked child a
+    // device might still look borrowed by a thread that only existed in the
+    // parent, so in that case the whole device is leaked instead.
+    fn forget(self, py: Python<'_>) {
+        let forgotten = match &self {
+            VirtualDevice::Camera(camera) => camera
+                .try_borrow_mut(py)
+                .map(|mut camera| camera.forget_device()),
+            VirtualDevice::Microphone(microphone) => microphone
+                .try_borrow_mut(py)
+                .map(|mut microphone| microphone.forget_device()),
+            VirtualDevice::Speaker(speaker) => speaker
+                .try_borrow_mut(py)
+                .map(|mut speaker| speaker.forget_device()),
+        };
+
+        if forgotten.is_err() {
+            std::mem::forget(self);
+        }
+    }
+}
+
+// A call client that failed to leave when shutting down (call client ID,
+// meeting URL and error), or a failure not related to a call client.
+type ShutdownFailure = (Option<u64>, Option<String>, String);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ContextState {
+    Uninitialized,
+    // The ID of the process that initialized the context. daily-core threads
+    // only exist in this process, so the context can't be used after a fork.
+    Initialized { pid: u32 },
+}
+
 pub(crate) struct DailyContext {
+    state: Mutex<ContextState>,
     request_id: AtomicU64,
-    device_manager: NativeDeviceManager,
+    call_client_id: AtomicU64,
+    device_manager: RwLock<Option<NativeDeviceManager>>,
+    devices: Mutex<HashMap<String, VirtualDevice>>,
+    call_clients: Mutex<HashMap<u64, Weak<CallClientHandle>>>,
+    metrics_server: Mutex<Option<MetricsServer>>,
 }
 
 impl DailyContext {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self {
+            state: Mutex::new(ContextState::Uninitialized),
+            request_id: AtomicU64::new(0),
+            call_client_id: AtomicU64::new(0),
+            device_manager: RwLock::new(None),
+            devices: Mutex::new(HashMap::new()),
+            call_clients: Mutex::new(HashMap::new()),
+            metrics_server: Mutex::new(None),
+        }
+    }
+
+    /// Initializes the context. The given function is expected to create the
+    /// native daily-core context.
+    pub fn init(&self, py: Python<'_>, create_context: impl FnOnce()) -> PyResult<()> {
+        let mut state = self.state.lock().unwrap();
+
+        match *state {
+            ContextState::Initialized { pid } if pid == process::id() => {
+                return Err(exceptions::PyRuntimeError::new_err(
+                    "Daily.init() has already been called",
+                ));
+            }
+            ContextState::Initialized { .. } => {
+                // We are in a forked child, so we can start from scratch.
+                self.forget_parent_context(py);
+            }
+            ContextState::Uninitialized => (),
+        }
+
+        create_context();
+
         let device_manager_ptr = unsafe { daily_core_context_create_device_manager() };
 
-        let device_manager = NativeDeviceManager::from(device_manager_ptr as *mut _);
+        *self.device_manager.write().unwrap() =
+            Some(NativeDeviceManager::from(device_manager_ptr as *mut _));
 
-        Self {
-            device_manager,
-            request_id: AtomicU64::new(0),
+        *state = ContextState::Initialized { pid: process::id() };
+
+        Ok(())
+    }
+
+    /// Deinitializes the context. All the live call clients leave their
+    /// meetings at the same time (waiting up to `EXIT_LEAVE_TIMEOUT` for all of
+    /// them) and are released and all virtual devices are destroyed. The context can be initialized again
+    /// afterwards. If a virtual device is being used by another thread this
+    /// fails and the context stays initialized, so it can be retried.
+    pub fn deinit(&self, py: Python<'_>) -> PyResult<()> {
+        // If the context was initialized by a parent process we can't destroy
+        // anything, the native threads don't exist in this process.
+        {
+            let mut state = self.state.lock().unwrap();
+            if let ContextState::Initialized { pid } = *state {
+                if pid != process::id() {
+                    self.forget_parent_context(py);
+                    *state = ContextState::Uninitialized;
+                    return Ok(());
+                }
+            }
+        }
+
+        self.check_initialized()?;
+
+        self.leave_and_release_all(py, Instant::now() + EXIT_LEAVE_TIMEOUT);
+
+        self.destroy(py)
+    }
+
+    /// Makes all the joined call clients leave their meetings and waits for
+    /// them to finish (up to the given timeout). Then, deinitializes the
+    /// context. Returns the clients that failed to leave and, if the context
+    /// couldn't be deinitialized, the reason (without a call client ID).
+    pub fn shutdown(&self, py: Python<'_>, timeout: Duration) -> PyResult<PyObject> {
+        self.check_initialized()?;
+
+        let mut failures = self.leave_and_release_all(py, Instant::now() + timeout);
+
+        // The clients are already released, so this doesn't wait for them
+        // again.
+        if let Err(error) = self.destroy(py) {
+            failures.push((
+                None,
+                None,
+                format!("unable to deinitialize: {}", error.value_bound(py)),
+            ));
+        }
+
+        let failures: Vec<PyObject> = failures
+            .into_iter()
+            .map(|(call_client_id, meeting_url, error)| {
+                let failure = PyDict::new_bound(py);
+                let _ = failure.set_item("callClientId", call_client_id);
+                let _ = failure.set_item("meetingUrl", meeting_url);
+                let _ = failure.set_item("error", error);
+                failure.into_py(py)
+            })
+            .collect();
+
+        Ok(failures.to_object(py))
+    }
+
+    // Makes all the joined call clients leave at the same time, so the
+    // deadline applies to all of them, and then releases all the call clients
+    // without leaving again. Returns the clients that failed to leave or
+    // didn't finish in time.
+    fn leave_and_release_all(&self, py: Python<'_>, deadline: Instant) -> Vec<ShutdownFailure> {
+        let call_clients = self.call_clients();
+
+        let leaving: Vec<(u64, Option<String>, mpsc::Receiver<CompletionResult>)> = call_clients
+            .iter()
+            .filter(|call_client| call_client.is_joined())
+            .filter_map(|call_client| {
+                call_client
+                    .leave()
+                    .map(|receiver| (call_client.id(), call_client.meeting_url(), receiver))
+            })
+            .collect();
+
+        // Leave completions need the GIL.
+        let failures: Vec<ShutdownFailure> = py.allow_threads(move || {
+            leaving
+                .into_iter()
+                .filter_map(|(call_client_id, meeting_url, receiver)| {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match receiver.recv_timeout(remaining) {
+                        Ok(Ok(_)) => None,
+                        Ok(Err(error)) => Some((Some(call_client_id), meeting_url, error)),
+                        Err(_) => {
+                            Some((Some(call_client_id), meeting_url, "timed out".to_string()))
+                        }
+                    }
+                })
+                .collect()
+        });
+
+        // Clients might have been released by someone else in the meantime.
+        for call_client in call_clients {
+            let _ = call_client.release(py);
+        }
+        self.call_clients.lock().unwrap().clear();
+
+        failures
+    }
+
+    // Destroys the virtual devices and the native context. Call clients
+    // should have been released already.
+    fn destroy(&self, py: Python<'_>) -> PyResult<()> {
+        self.stop_metrics_server(py);
+
+        // Closing devices might call completions, so don't hold the state
+        // lock either.
+        let devices: Vec<(String, VirtualDevice)> = self.devices.lock().unwrap().drain().collect();
+
+        let mut close_error = None;
+        let mut remaining = Vec::new();
+
+        for (device_name, device) in devices {
+            if close_error.is_none() {
+                match device.close(py) {
+                    Ok(()) => continue,
+                    Err(error) => close_error = Some(error),
+                }
+            }
+            remaining.push((device_name, device));
+        }
+
+        // Keep the devices we haven't closed, the native context can't be
+        // destroyed while they are being used.
+        if let Some(error) = close_error {
+            self.devices.lock().unwrap().extend(remaining);
+            return Err(error);
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        // Someone else might have deinitialized while we were releasing.
+        if *state == ContextState::Uninitialized {
+            return Ok(());
         }
+
+        self.device_manager.write().unwrap().take();
+
+        unsafe { daily_core_context_destroy() };
+
+        *state = ContextState::Uninitialized;
+
+        Ok(())
+    }
+
+    pub fn check_initialized(&self) -> PyResult<()> {
+        match *self.state.lock().unwrap() {
+            ContextState::Initialized { pid } if pid == process::id() => Ok(()),
+            ContextState::Initialized { .. } => Err(exceptions::PyRuntimeError::new_err(
+                "Daily.init() was called in a parent process, call Daily.init() again after forking",
+            )),
+            ContextState::Uninitialized => Err(exceptions::PyRuntimeError::new_err(
+                "Daily.init() has not been called",
+            )),
+        }
+    }
+
+    // Drops everything that was created by the parent process without calling
+    // into daily-core. Native objects are leaked since their threads don't
+    // exist in this process and destroying them might deadlock.
+    fn forget_parent_context(&self, py: Python<'_>) {
+        for call_client in self.call_clients() {
+            call_client.forget();
+        }
+        self.call_clients.lock().unwrap().clear();
+
+        let devices: Vec<VirtualDevice> = self
+            .devices
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, device)| device)
+            .collect();
+
+        for device in devices {
+            device.forget(py);
+        }
+
+        if let Some(device_manager) = self.device_manager.write().unwrap().take() {
+            std::mem::forget(device_manager);
+        }
+
+        // The child might have started its own metrics server already.
+        Self::forget_parent_metrics_server(&mut self.metrics_server.lock().unwrap());
     }
 
     pub fn next_request_id(&self) -> u64 {
         self.request_id.fetch_add(1, Ordering::SeqCst)
     }
 
+    pub fn next_call_client_id(&self) -> u64 {
+        self.call_client_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    pub fn register_call_client(&self, call_client: &Arc<CallClientHandle>) {
+        self.call_clients
+            .lock()
+            .unwrap()
+            .insert(call_client.id(), Arc::downgrade(call_client));
+    }
+
+    pub fn unregister_call_client(&self, call_client_id: u64) {
+        self.call_clients.lock().unwrap().remove(&call_client_id);
+    }
+
+    /// Returns the call client with the given ID if it has not been released.
+    pub fn call_client(&self, call_client_id: u64) -> Option<Arc<CallClientHandle>> {
+        self.call_clients
+            .lock()
+            .unwrap()
+            .get(&call_client_id)
+            .and_then(Weak::upgrade)
+    }
+
+    /// Returns all the call clients that have not been released yet.
+    pub fn call_clients(&self) -> Vec<Arc<CallClientHandle>> {
+        self.call_clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    pub fn metrics_text(&self) -> String {
+        let entries: Vec<_> = self
+            .call_clients()
+            .iter()
+            .map(|call_client| call_client.metrics())
+            .collect();
+
+        let num_devices = self.devices.lock().unwrap().len();
+
+        render_metrics(&entries, num_devices)
+    }
+
+    pub fn serve_metrics(&self, host: &str, port: u16) -> PyResult<u16> {
+        let mut metrics_server = self.metrics_server.lock().unwrap();
+
+        // The server thread of a parent process doesn't exist here.
+        Self::forget_parent_metrics_server(&mut metrics_server);
+
+        if let Some(server) = metrics_server.as_ref() {
+            return Err(exceptions::PyRuntimeError::new_err(format!(
+                "metrics are already being served on port {}",
+                server.port()
+            )));
+        }
+
+        let server = MetricsServer::start(host, port).map_err(|error| {
+            exceptions::PyIOError::new_err(format!("unable to serve metrics: {error}"))
+        })?;
+
+        let port = server.port();
+
+        *metrics_server = Some(server);
+
+        Ok(port)
+    }
+
+    fn forget_parent_metrics_server(metrics_server: &mut Option<MetricsServer>) {
+        if metrics_server
+            .as_ref()
+            .is_some_and(MetricsServer::is_from_parent)
+        {
+            if let Some(server) = metrics_server.take() {
+                server.forget();
+            }
+        }
+    }
+
+    fn stop_metrics_server(&self, py: Python<'_>) {
+        let metrics_server = self.metrics_server.lock().unwrap().take();
+
+        if let Some(server) = metrics_server {
+            if server.is_from_parent() {
+                server.forget();
+            } else {
+                // The server might be waiting for a slow client.
+                py.allow_threads(move || server.stop());
+            }
+        }
+    }
+
+    fn with_device_manager<T>(&self, f: impl FnOnce(&NativeDeviceManager) -> T) -> PyResult<T> {
+        if let Some(device_manager) = self.device_manager.read().unwrap().as_ref() {
+            Ok(f(device_manager))
+        } else {
+            Err(exceptions::PyRuntimeError::new_err(
+                "Daily.init() has not been called",
+            ))
+        }
+    }
+
     pub fn get_enumerated_devices(&self) -> *mut libc::c_char {
         const EMPTY: &[u8] = b"[]\0";
 
-        let devices = unsafe {
-            daily_core_context_device_manager_enumerated_devices(
-                self.device_manager.as_ptr() as *const _
-            )
-        };
+        let devices = self
+            .with_device_manager(|device_manager| unsafe {
+                daily_core_context_device_manager_enumerated_devices(
+                    device_manager.as_ptr() as *const _
+                )
+            })
+            .unwrap_or(ptr::null_mut());
 
         if devices.is_null() {
             EMPTY.as_ptr().cast_mut() as *mut _
@@ -80,16 +473,17 @@ impl DailyContext {
         network_thread: *mut WebrtcThread,
         constraints: *const libc::c_char,
     ) -> *mut libc::c_void {
-        unsafe {
+        self.with_device_manager(|device_manager| unsafe {
             daily_core_context_device_manager_get_user_media(
-                self.device_manager.as_ptr() as *mut _,
+                device_manager.as_ptr() as *mut _,
                 peer_connection_factory,
                 signaling_thread,
                 worker_thread,
                 network_thread,
                 constraints,
             )
-        }
+        })
+        .unwrap_or(ptr::null_mut())
     }
 
     #[allow(clippy::missing_safety_doc)]
@@ -97,21 +491,26 @@ impl DailyContext {
         &self,
         task_queue_factory: *mut WebrtcTaskQueueFactory,
     ) -> *mut WebrtcAudioDeviceModule {
-        unsafe {
+        self.with_device_manager(|device_manager| unsafe {
             daily_core_context_create_audio_device_module(
-                self.device_manager.as_ptr() as *mut _,
+                device_manager.as_ptr() as *mut _,
                 task_queue_factory,
             )
-        }
+        })
+        .unwrap_or(ptr::null_mut())
     }
 
     pub fn create_camera_device(
         &self,
+        py: Python<'_>,
         device_name: &str,
         width: u32,
         height: u32,
         color_format: &str,
-    ) -> PyResult<PyVirtualCameraDevice> {
+    ) -> PyResult<Py<PyVirtualCameraDevice>> {
+        self.check_initialized()?;
+        self.check_device_name(device_name)?;
+
         let device_name_cstr =
             CString::new(device_name).expect("invalid virtual camera device name string");
         let color_format_cstr = CString::new(color_format).expect("invalid color format string");
@@ -120,9 +519,9 @@ impl DailyContext {
             let mut py_device =
                 PyVirtualCameraDevice::new(device_name, width, height, color_format);
 
-            unsafe {
+            self.with_device_manager(|device_manager| unsafe {
                 let camera_device = daily_core_context_create_virtual_camera_device(
-                    self.device_manager.as_ptr() as *mut _,
+                    device_manager.as_ptr() as *mut _,
                     device_name_cstr.as_ptr(),
                     width,
                     height,
@@ -131,7 +530,11 @@ impl DailyContext {
 
                 py_device
                     .attach_camera_device(NativeVirtualCameraDevice::from(camera_device as *mut _));
-            }
+            })?;
+
+            let py_device = Py::new(py, py_device)?;
+
+            self.register_device(device_name, VirtualDevice::Camera(py_device.clone_ref(py)));
 
             Ok(py_device)
         } else {
@@ -143,20 +546,24 @@ impl DailyContext {
 
     pub fn create_speaker_device(
         &self,
+        py: Python<'_>,
         device_name: &str,
         sample_rate: u32,
         channels: u8,
         non_blocking: bool,
-    ) -> PyResult<PyVirtualSpeakerDevice> {
+    ) -> PyResult<Py<PyVirtualSpeakerDevice>> {
+        self.check_initialized()?;
+        self.check_device_name(device_name)?;
+
         let device_name_cstr =
             CString::new(device_name).expect("invalid virtual speaker device name string");
 
         let mut py_device =
             PyVirtualSpeakerDevice::new(device_name, sample_rate, channels, non_blocking);
 
-        unsafe {
+        self.with_device_manager(|device_manager| unsafe {
             let speaker_device = daily_core_context_create_virtual_speaker_device(
-                self.device_manager.as_ptr() as *mut _,
+                device_manager.as_ptr() as *mut _,
                 device_name_cstr.as_ptr(),
                 sample_rate,
                 channels,
@@ -164,26 +571,34 @@ impl DailyContext {
             );
 
             py_device.attach_audio_device(NativeVirtualSpeakerDevice::from(speaker_device));
-        }
+        })?;
+
+        let py_device = Py::new(py, py_device)?;
+
+        self.register_device(device_name, VirtualDevice::Speaker(py_device.clone_ref(py)));
 
         Ok(py_device)
     }
 
     pub fn create_microphone_device(
         &self,
+        py: Python<'_>,
         device_name: &str,
         sample_rate: u32,
         channels: u8,
         non_blocking: bool,
-    ) -> PyResult<PyVirtualMicrophoneDevice> {
+    ) -> PyResult<Py<PyVirtualMicrophoneDevice>> {
+        self.check_initialized()?;
+        self.check_device_name(device_name)?;
+
         let device_name_cstr =
             CString::new(device_name).expect("invalid virtual microphone device name string");
 
         let mut py_device = PyVirtualMicrophoneDevice::new(device_name, sample_rate, channels);
 
-        unsafe {
+        self.with_device_manager(|device_manager| unsafe {
             let microphone_device = daily_core_context_create_virtual_microphone_device(
-                self.device_manager.as_ptr() as *mut _,
+                device_manager.as_ptr() as *mut _,
                 device_name_cstr.as_ptr(),
                 sample_rate,
                 channels,
@@ -191,17 +606,93 @@ impl DailyContext {
             );
 
             py_device.attach_audio_device(NativeVirtualMicrophoneDevice::from(microphone_device));
-        }
+        })?;
+
+        let py_device = Py::new(py, py_device)?;
+
+        self.register_device(
+            device_name,
+            VirtualDevice::Microphone(py_device.clone_ref(py)),
+        );
 
         Ok(py_device)
     }
 
+    pub fn list_devices(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.check_initialized()?;
+
+        let devices = self.devices.lock().unwrap();
+
+        // Keep the list in a predictable order.
+        let mut device_names: Vec<&String> = devices.keys().collect();
+        device_names.sort();
+
+        // Devices might be mutably borrowed by another thread (e.g. while
+        // writing frames with the GIL released), so don't panic.
+        let device_list = device_names
+            .into_iter()
+            .map(|device_name| match &devices[device_name] {
+                VirtualDevice::Camera(camera) => Ok(camera.try_borrow(py)?.device_info(py)),
+                VirtualDevice::Microphone(microphone) => {
+                    Ok(microphone.try_borrow(py)?.device_info(py))
+                }
+                VirtualDevice::Speaker(speaker) => Ok(speaker.try_borrow(py)?.device_info(py)),
+            })
+            .collect::<PyResult<Vec<PyObject>>>()?;
+
+        Ok(device_list.to_object(py))
+    }
+
+    pub fn destroy_device(&self, py: Python<'_>, device_name: &str) -> PyResult<()> {
+        self.check_initialized()?;
+
+        // Don't lock in the if statement otherwise the lock is held while the
+        // device is being closed.
+        let device = self.devices.lock().unwrap().remove(device_name);
+
+        if let Some(device) = device {
+            if let Err(error) = device.close(py) {
+                // The device is still being used, so it's still there.
+                self.register_device(device_name, device);
+                return Err(error);
+            }
+            Ok(())
+        } else {
+            Err(exceptions::PyValueError::new_err(format!(
+                "virtual device '{device_name}' does not exist"
+            )))
+        }
+    }
+
+    pub fn unregister_device(&self, device_name: &str) {
+        self.devices.lock().unwrap().remove(device_name);
+    }
+
+    fn register_device(&self, device_name: &str, device: VirtualDevice) {
+        self.devices
+            .lock()
+            .unwrap()
+            .insert(device_name.to_string(), device);
+    }
+
+    fn check_device_name(&self, device_name: &str) -> PyResult<()> {
+        if self.devices.lock().unwrap().contains_key(device_name) {
+            Err(exceptions::PyValueError::new_err(format!(
+                "virtual device '{device_name}' already exists"
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn create_native_vad(
         &self,
         reset_period_ms: u32,
         sample_rate: u32,
         channels: u8,
     ) -> PyResult<PyNativeVad> {
+        self.check_initialized()?;
+
         let mut py_vad = PyNativeVad::new(reset_period_ms, sample_rate, channels);
 
         unsafe {
@@ -214,15 +705,17 @@ impl DailyContext {
     }
 
     pub fn select_speaker_device(&self, device_name: &str) -> PyResult<()> {
+        self.check_initialized()?;
+
         let device_name_cstr =
             CString::new(device_name).expect("invalid virtual speaker device name string");
 
-        let selected = unsafe {
+        let selected = self.with_device_manager(|device_manager| unsafe {
             daily_core_context_select_speaker_device(
-                self.device_manager.as_ptr() as *mut _,
+                device_manager.as_ptr() as *mut _,
                 device_name_cstr.as_ptr(),
             )
-        };
+        })?;
 
         if selected {
             Ok(())
@@ -236,11 +729,13 @@ impl DailyContext {
     pub fn get_selected_microphone_device(&self) -> *const libc::c_char {
         const EMPTY: &[u8] = b"\0";
 
-        let device = unsafe {
-            daily_core_context_get_selected_microphone_device(
-                self.device_manager.as_ptr() as *const _
-            )
-        };
+        let device = self
+            .with_device_manager(|device_manager| unsafe {
+                daily_core_context_get_selected_microphone_device(
+                    device_manager.as_ptr() as *const _
+                )
+            })
+            .unwrap_or(ptr::null_mut());
 
         if device.is_null() {
             EMPTY.as_ptr().cast()
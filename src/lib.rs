@@ -4,27 +4,29 @@ extern crate lazy_static;
 pub(crate) mod call_client;
 pub(crate) mod context;
 pub(crate) mod media;
+pub(crate) mod metrics;
 pub(crate) mod util;
 
 use call_client::{PyCallClient, PyEventHandler};
 use context::GLOBAL_CONTEXT;
 use media::{
-    PyAudioData, PyNativeVad, PyVideoFrame, PyVirtualCameraDevice, PyVirtualMicrophoneDevice,
-    PyVirtualSpeakerDevice,
+    PyAudioData, PyAudioResampler, PyNativeVad, PyVideoFrame, PyVirtualCameraDevice,
+    PyVirtualMicrophoneDevice, PyVirtualSpeakerDevice,
 };
 
 use std::env;
 use std::ffi::CString;
 use std::ptr;
+use std::time::Duration;
 
 use daily_core::prelude::{
-    daily_core_context_create_with_threads, daily_core_context_destroy, daily_core_set_log_level,
-    LogLevel, NativeAboutClient, NativeContextDelegate, NativeContextDelegatePtr,
-    NativeRawWebRtcContextDelegate, NativeWebRtcContextDelegate, NativeWebRtcContextDelegateFns,
-    NativeWebRtcContextDelegatePtr, WebrtcAudioDeviceModule, WebrtcPeerConnectionFactory,
-    WebrtcTaskQueueFactory, WebrtcThread,
+    daily_core_context_create_with_threads, daily_core_set_log_level, LogLevel, NativeAboutClient,
+    NativeContextDelegate, NativeContextDelegatePtr, NativeRawWebRtcContextDelegate,
+    NativeWebRtcContextDelegate, NativeWebRtcContextDelegateFns, NativeWebRtcContextDelegatePtr,
+    WebrtcAudioDeviceModule, WebrtcPeerConnectionFactory, WebrtcTaskQueueFactory, WebrtcThread,
 };
 
+use pyo3::exceptions;
 use pyo3::prelude::*;
 
 const DAILY_PYTHON_NAME: &str = "daily-python";
@@ -70,6 +72,49 @@ unsafe extern "C" fn create_audio_device_module(
     GLOBAL_CONTEXT.create_audio_device_module(task_queue_factory)
 }
 
+fn create_native_context(worker_threads: usize) {
+    unsafe {
+        daily_core_set_log_level(LogLevel::Off);
+    }
+
+    let library_cstr = CString::new(DAILY_PYTHON_NAME).expect("invalid library string");
+    let version_cstr = CString::new(DAILY_PYTHON_VERSION).expect("invalid version string");
+    let os_cstr = CString::new(env::consts::OS).expect("invalid OS string");
+
+    let about_client = NativeAboutClient::new(
+        library_cstr.as_ptr(),
+        version_cstr.as_ptr(),
+        os_cstr.as_ptr(),
+        ptr::null(),
+    );
+
+    let context_delegate =
+        NativeContextDelegate::new(NativeContextDelegatePtr::new(ptr::null_mut()));
+
+    let webrtc_delegate = NativeWebRtcContextDelegate::new(
+        NativeWebRtcContextDelegatePtr::new(ptr::null_mut()),
+        NativeWebRtcContextDelegateFns::new(
+            get_user_media,
+            None,
+            get_enumerated_devices,
+            Some(create_audio_device_module),
+            None,
+            None,
+            None,
+            None,
+            get_audio_device,
+            set_audio_device,
+        ),
+    );
+
+    daily_core_context_create_with_threads(
+        context_delegate,
+        webrtc_delegate,
+        about_client,
+        worker_threads,
+    );
+}
+
 /// This class is used to initialize the SDK and create virtual devices.
 #[pyclass(name = "Daily", module = "daily")]
 struct PyDaily;
@@ -79,59 +124,85 @@ impl PyDaily {
     /// Initializes the SDK. This function needs to be called before anything
     /// else, usually done at the application startup.
     ///
+    /// The SDK can be initialized again after calling :func:`deinit`. An
+    /// exception is raised if the SDK is already initialized.
+    ///
+    /// The SDK can't be used in a child process (i.e. after `os.fork()`) if it
+    /// was initialized by the parent process. The child process needs to call
+    /// this function again. Call clients and virtual devices created by the
+    /// parent process can't be used in the child process.
+    ///
     /// :param int worker_threads: Number of internal worker threads. Increasing this number might be necessary if the application needs to create a large number of concurrent call clients
     #[staticmethod]
     #[pyo3(signature = (worker_threads = 2))]
-    pub fn init(worker_threads: usize) {
-        unsafe {
-            daily_core_set_log_level(LogLevel::Off);
-        }
-
-        let library_cstr = CString::new(DAILY_PYTHON_NAME).expect("invalid library string");
-        let version_cstr = CString::new(DAILY_PYTHON_VERSION).expect("invalid version string");
-        let os_cstr = CString::new(env::consts::OS).expect("invalid OS string");
-
-        let about_client = NativeAboutClient::new(
-            library_cstr.as_ptr(),
-            version_cstr.as_ptr(),
-            os_cstr.as_ptr(),
-            ptr::null(),
-        );
-
-        let context_delegate =
-            NativeContextDelegate::new(NativeContextDelegatePtr::new(ptr::null_mut()));
-
-        let webrtc_delegate = NativeWebRtcContextDelegate::new(
-            NativeWebRtcContextDelegatePtr::new(ptr::null_mut()),
-            NativeWebRtcContextDelegateFns::new(
-                get_user_media,
-                None,
-                get_enumerated_devices,
-                Some(create_audio_device_module),
-                None,
-                None,
-                None,
-                None,
-                get_audio_device,
-                set_audio_device,
-            ),
-        );
-
-        daily_core_context_create_with_threads(
-            context_delegate,
-            webrtc_delegate,
-            about_client,
-            worker_threads,
-        );
+    pub fn init(py: Python<'_>, worker_threads: usize) -> PyResult<()> {
+        GLOBAL_CONTEXT.init(py, || create_native_context(worker_threads))
     }
 
     /// Deallocates SDK resources. This is usually called when shutting down the
     /// application.
+    ///
+    /// All call clients that have not been released leave their meetings
+    /// (waiting up to 10 seconds for all of them to leave) and are released,
+    /// and all virtual devices are destroyed. An exception is raised if the SDK is not
+    /// initialized or if a virtual device is being used by another thread, in
+    /// which case this can be called again.
+    #[staticmethod]
+    pub fn deinit(py: Python<'_>) -> PyResult<()> {
+        GLOBAL_CONTEXT.deinit(py)
+    }
+
+    /// Makes all call clients that are in a meeting leave at the same time
+    /// and waits for them to finish, up to the given timeout. Then, releases
+    /// all call clients (without waiting for them to leave again) and
+    /// deinitializes the SDK (see :func:`deinit`). This is useful when the
+    /// application is being terminated (e.g. on `SIGTERM`).
+    ///
+    /// The failures are always returned. If the SDK can't be deinitialized
+    /// (e.g. a virtual device is being used by another thread), the reason is
+    /// returned as a failure without a call client ID and the SDK stays
+    /// initialized, so this can be called again.
+    ///
+    /// :param float timeout: Maximum time in seconds to wait for the call clients to leave
+    ///
+    /// :return: The call clients that failed to leave or didn't finish in time. See :ref:`ShutdownFailure`
+    /// :rtype: List[Mapping[str, Any]]
+    #[staticmethod]
+    #[pyo3(signature = (timeout = 5.0))]
+    pub fn shutdown(py: Python<'_>, timeout: f64) -> PyResult<PyObject> {
+        if !timeout.is_finite() || timeout < 0.0 {
+            return Err(exceptions::PyValueError::new_err(
+                "timeout should be a non-negative number of seconds",
+            ));
+        }
+
+        GLOBAL_CONTEXT.shutdown(py, Duration::from_secs_f64(timeout))
+    }
+
+    /// Returns the SDK metrics in the Prometheus text exposition format. This
+    /// includes network statistics, participant counts, received audio and
+    /// video frames and request durations of every call client.
+    ///
+    /// :return: The metrics in the Prometheus text format
+    /// :rtype: str
+    #[staticmethod]
+    pub fn metrics_text() -> String {
+        GLOBAL_CONTEXT.metrics_text()
+    }
+
+    /// Starts a local HTTP server in a background thread that serves the SDK
+    /// metrics (see :func:`metrics_text`) on `/metrics`. The server runs until
+    /// :func:`deinit` is called and can only be started once at a time.
+    ///
+    /// :param int port: The port to listen on. Use 0 to pick any available port
+    /// :param str host: The address to listen on. Use "0.0.0.0" to accept remote connections
+    ///
+    /// :return: The port the server is listening on
+    /// :rtype: int
     #[staticmethod]
-    pub fn deinit() {
-        // TODO(aleix): We need to make sure all clients leave before doing this
-        // otherwise we might crash.
-        unsafe { daily_core_context_destroy() };
+    #[pyo3(signature = (port, host = "127.0.0.1"))]
+    pub fn serve_metrics(port: u16, host: &str) -> PyResult<u16> {
+        GLOBAL_CONTEXT.serve_metrics(host, port)
     }
 
     /// Creates a new virtual camera device. Camera devices are used to
@@ -147,12 +218,13 @@ impl PyDaily {
     #[staticmethod]
     #[pyo3(signature = (device_name, width, height, color_format = "RGBA"))]
     pub fn create_camera_device(
+        py: Python<'_>,
         device_name: &str,
         width: u32,
         height: u32,
         color_format: &str,
-    ) -> PyResult<PyVirtualCameraDevice> {
-        GLOBAL_CONTEXT.create_camera_device(device_name, width, height, color_format)
+    ) -> PyResult<Py<PyVirtualCameraDevice>> {
+        GLOBAL_CONTEXT.create_camera_device(py, device_name, width, height, color_format)
     }
 
     /// Creates a new virtual speaker device. Speaker devices are used to
@@ -171,12 +243,13 @@ impl PyDaily {
     #[staticmethod]
     #[pyo3(signature = (device_name, sample_rate = 16000, channels = 1, non_blocking = false))]
     pub fn create_speaker_device(
+        py: Python<'_>,
         device_name: &str,
         sample_rate: u32,
         channels: u8,
         non_blocking: bool,
-    ) -> PyResult<PyVirtualSpeakerDevice> {
-        GLOBAL_CONTEXT.create_speaker_device(device_name, sample_rate, channels, non_blocking)
+    ) -> PyResult<Py<PyVirtualSpeakerDevice>> {
+        GLOBAL_CONTEXT.create_speaker_device(py, device_name, sample_rate, channels, non_blocking)
     }
 
     /// Creates a new virtual microphone device. Microphone devices are used to
@@ -184,6 +257,16 @@ impl PyDaily {
     ///
     /// Microphone devices are selected with :func:`CallClient.update_inputs`.
     ///
+    /// Audio written to the device is not processed by the device itself. To
+    /// enable echo cancellation, noise suppression or automatic gain control,
+    /// set the `echoCancellation`, `noiseSuppression` or `autoGainControl`
+    /// `customConstraints` of the microphone input settings (see
+    /// :ref:`AudioInputSettings`). These are applied by WebRTC's audio
+    /// processing to the microphone track, and echo cancellation already uses
+    /// the audio played by the selected virtual speaker as the far-end
+    /// reference, so processing the audio in the device as well would
+    /// process it twice.
+    ///
     /// :param str device_name: The virtual microphone device name. This can be used as a `deviceId` when configuring the call client inputs
     /// :param int sample_rate: Sample rate
     /// :param int channels: Number of channels (2 for stereo, 1 for mono)
@@ -194,12 +277,19 @@ impl PyDaily {
     #[staticmethod]
     #[pyo3(signature = (device_name, sample_rate = 16000, channels = 1, non_blocking = false))]
     pub fn create_microphone_device(
+        py: Python<'_>,
         device_name: &str,
         sample_rate: u32,
         channels: u8,
         non_blocking: bool,
-    ) -> PyResult<PyVirtualMicrophoneDevice> {
-        GLOBAL_CONTEXT.create_microphone_device(device_name, sample_rate, channels, non_blocking)
+    ) -> PyResult<Py<PyVirtualMicrophoneDevice>> {
+        GLOBAL_CONTEXT.create_microphone_device(
+            py,
+            device_name,
+            sample_rate,
+            channels,
+            non_blocking,
+        )
     }
 
     /// Selects one of the previously created virtual speaker devices to be the
@@ -208,12 +298,36 @@ impl PyDaily {
     /// audio from all the participants will be mixed and that's the audio that
     /// is received in the speaker.
     ///
+    /// The selected speaker is shared by all the call clients in the process,
+    /// it can't be bound to a single call client (see
+    /// :func:`CallClient.set_devices`).
+    ///
     /// :param str device_name: The name of the virtual speaker device to select
     #[staticmethod]
     pub fn select_speaker_device(device_name: &str) -> PyResult<()> {
         GLOBAL_CONTEXT.select_speaker_device(device_name)
     }
 
+    /// Returns the list of virtual devices that have been created and not yet
+    /// destroyed.
+    ///
+    /// :return: See :ref:`VirtualDeviceInfo`
+    /// :rtype: List[Mapping[str, Any]]
+    #[staticmethod]
+    pub fn list_devices(py: Python<'_>) -> PyResult<PyObject> {
+        GLOBAL_CONTEXT.list_devices(py)
+    }
+
+    /// Destroys a previously created virtual device. The device can't be used
+    /// after it has been destroyed and its name can be reused to create a new
+    /// device.
+    ///
+    /// :param str device_name: The name of the virtual device to destroy
+    #[staticmethod]
+    pub fn destroy_device(py: Python<'_>, device_name: &str) -> PyResult<()> {
+        GLOBAL_CONTEXT.destroy_device(py, device_name)
+    }
+
     /// Creates a new VAD analyzer. VADs are used to detect speech from an audio
     /// stream.
     ///
@@ -238,6 +352,7 @@ impl PyDaily {
 #[pymodule]
 fn daily(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAudioData>()?;
+    m.add_class::<PyAudioResampler>()?;
     m.add_class::<PyCallClient>()?;
     m.add_class::<PyDaily>()?;
     m.add_class::<PyEventHandler>()?;
@@ -1,7 +1,24 @@
+use crate::util::audio::{
+    downmix_to_mono, f32_to_bytes, resample, samples_from_bytes, samples_to_bytes, samples_to_f32,
+    AudioLevel,
+};
+
+use pyo3::exceptions;
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 
 /// This class represents received audio data. It contains a bytestring with the
 /// audio frames and other attributes such as bits per sample and sample rate.
+///
+/// Audio data can also be created from a bytestring of 16-bit linear PCM
+/// samples, which is useful for testing or to process audio before writing it
+/// to a virtual microphone.
+///
+/// :param bytestring audio_frames: A bytestring with 16-bit linear PCM audio frames
+/// :param int sample_rate: Sample rate
+/// :param int num_channels: Number of channels (2 for stereo, 1 for mono)
+/// :param int timestamp_us: The time in microseconds that the audio frames were received, from the system's monotonic clock
+/// :param int sample_offset: The number of audio frames that preceded these ones in the same stream (i.e. delivered to the same audio renderer)
 #[pyclass(name = "AudioData", module = "daily", get_all)]
 pub struct PyAudioData {
     /// The bits per sample of the audio data
@@ -14,4 +31,190 @@ pub struct PyAudioData {
     pub num_audio_frames: usize,
     /// A bytestring with the audio frames
     pub audio_frames: PyObject,
+    /// The time in microseconds that the audio data was received, from the
+    /// system's monotonic clock (`CLOCK_MONOTONIC`). The clock is sampled
+    /// locally when daily-core delivers the audio, it's not derived from RTP
+    /// timestamps. This is a different clock than the one used for
+    /// :attr:`VideoFrame.timestamp_us`, so they can't be compared
+    pub timestamp_us: i64,
+    /// The number of audio frames delivered to the same audio renderer before
+    /// this audio data. This is counted locally when audio is received, so it
+    /// doesn't account for audio lost in the network and it starts again from
+    /// 0 if the audio renderer is set again
+    pub sample_offset: u64,
+}
+
+impl PyAudioData {
+    pub(crate) fn from_samples(
+        &self,
+        py: Python<'_>,
+        samples: &[i16],
+        sample_rate: u32,
+        num_channels: usize,
+    ) -> Self {
+        let bytes = samples_to_bytes(samples);
+
+        // Keep the sample offset in the new sample rate.
+        let sample_offset =
+            (self.sample_offset as u128 * sample_rate as u128 / self.sample_rate as u128) as u64;
+
+        Self {
+            bits_per_sample: 16,
+            sample_rate,
+            num_channels,
+            num_audio_frames: samples.len() / num_channels,
+            audio_frames: PyBytes::new_bound(py, &bytes).into_py(py),
+            timestamp_us: self.timestamp_us,
+            sample_offset,
+        }
+    }
+
+    pub(crate) fn samples(&self, py: Python<'_>) -> PyResult<Vec<i16>> {
+        // All the processing functions work with 16-bit linear PCM.
+        if self.bits_per_sample != 16 {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "unsupported bits per sample {} (only 16-bit samples are supported)",
+                self.bits_per_sample
+            )));
+        }
+
+        if self.num_channels == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "audio data has no channels",
+            ));
+        }
+
+        if self.sample_rate == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "audio data has no sample rate",
+            ));
+        }
+
+        let audio_frames = self.audio_frames.bind(py).downcast::<PyBytes>()?;
+
+        Ok(samples_from_bytes(audio_frames.as_bytes()))
+    }
+}
+
+#[pymethods]
+impl PyAudioData {
+    #[new]
+    #[pyo3(signature = (audio_frames, sample_rate = 16000, num_channels = 1, timestamp_us = 0, sample_offset = 0))]
+    pub fn new(
+        py: Python<'_>,
+        audio_frames: &Bound<'_, PyBytes>,
+        sample_rate: u32,
+        num_channels: usize,
+        timestamp_us: i64,
+        sample_offset: u64,
+    ) -> PyResult<Self> {
+        let num_bytes = audio_frames.len()?;
+        let bytes_per_sample = 2;
+
+        if sample_rate == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "sample rate should be greater than 0",
+            ));
+        }
+
+        if num_channels == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "number of channels should be greater than 0",
+            ));
+        }
+
+        if num_bytes % (bytes_per_sample * num_channels) != 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "audio frames bytestring should contain 16-bit samples for all channels",
+            ));
+        }
+
+        Ok(Self {
+            bits_per_sample: 16,
+            sample_rate,
+            num_channels,
+            num_audio_frames: (num_bytes / bytes_per_sample) / num_channels,
+            audio_frames: audio_frames.clone().into_py(py),
+            timestamp_us,
+            sample_offset,
+        })
+    }
+
+    /// Returns the duration of the audio frames in milliseconds.
+    ///
+    /// :return: The duration in milliseconds
+    /// :rtype: float
+    #[getter]
+    fn duration_ms(&self) -> f64 {
+        if self.sample_rate == 0 {
+            0.0
+        } else {
+            (self.num_audio_frames as f64 * 1000.0) / self.sample_rate as f64
+        }
+    }
+
+    /// Returns the RMS and peak levels of the audio frames.
+    ///
+    /// :return: See :ref:`AudioLevel`
+    /// :rtype: dict
+    #[getter]
+    fn level(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let samples = self.samples(py)?;
+
+        let level = py.allow_threads(move || AudioLevel::from_samples(&samples));
+
+        Ok(level.to_object(py))
+    }
+
+    /// Converts the audio frames to 32-bit floating point samples in the
+    /// range [-1.0, 1.0]. Channels are kept interleaved.
+    ///
+    /// :return: A bytestring with the 32-bit float samples (native endianness)
+    /// :rtype: bytestring
+    fn to_float32(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let samples = self.samples(py)?;
+
+        let floats = py.allow_threads(move || f32_to_bytes(&samples_to_f32(&samples)));
+
+        Ok(PyBytes::new_bound(py, &floats).into_py(py))
+    }
+
+    /// Resamples the audio frames to the given sample rate. The audio is
+    /// low-pass filtered first, so downsampling doesn't introduce aliasing.
+    /// Use :class:`AudioResampler` to resample consecutive audio data of a
+    /// stream.
+    ///
+    /// :param int sample_rate: The new sample rate
+    ///
+    /// :return: New resampled audio data
+    /// :rtype: :class:`AudioData`
+    fn resample(&self, py: Python<'_>, sample_rate: u32) -> PyResult<Self> {
+        if sample_rate == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "sample rate should be greater than 0",
+            ));
+        }
+
+        let samples = self.samples(py)?;
+        let num_channels = self.num_channels;
+        let from_rate = self.sample_rate;
+
+        let resampled =
+            py.allow_threads(move || resample(&samples, num_channels, from_rate, sample_rate));
+
+        Ok(self.from_samples(py, &resampled, sample_rate, self.num_channels))
+    }
+
+    /// Mixes all the channels of the audio frames into a single channel.
+    ///
+    /// :return: New mono audio data
+    /// :rtype: :class:`AudioData`
+    fn to_mono(&self, py: Python<'_>) -> PyResult<Self> {
+        let samples = self.samples(py)?;
+        let num_channels = self.num_channels;
+
+        let mono = py.allow_threads(move || downmix_to_mono(&samples, num_channels));
+
+        Ok(self.from_samples(py, &mono, self.sample_rate, 1))
+    }
 }
@@ -0,0 +1,85 @@
+use crate::media::PyAudioData;
+use crate::util::audio::Resampler;
+
+use pyo3::exceptions;
+use pyo3::prelude::*;
+
+/// This class resamples a stream of audio data (e.g. the audio data received
+/// by an audio renderer). Unlike :func:`AudioData.resample`, the resampler
+/// keeps the last audio frames between calls, so consecutive audio data is
+/// resampled without discontinuities. Because of this the output is delayed by
+/// a couple of milliseconds.
+///
+/// :param int sample_rate: The sample rate to resample to
+#[pyclass(name = "AudioResampler", module = "daily")]
+pub struct PyAudioResampler {
+    sample_rate: u32,
+    // The resampler with the sample rate and number of channels of the input
+    // it was created for.
+    resampler: Option<(Resampler, u32, usize)>,
+}
+
+#[pymethods]
+impl PyAudioResampler {
+    #[new]
+    pub fn new(sample_rate: u32) -> PyResult<Self> {
+        if sample_rate == 0 {
+            return Err(exceptions::PyValueError::new_err(
+                "sample rate should be greater than 0",
+            ));
+        }
+
+        Ok(Self {
+            sample_rate,
+            resampler: None,
+        })
+    }
+
+    /// Returns the sample rate audio data is resampled to.
+    ///
+    /// :return: The sample rate
+    /// :rtype: int
+    #[getter]
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Resamples the given audio data, which is expected to follow the audio
+    /// data of the previous call. If the sample rate or the number of channels
+    /// of the audio data changes the resampler starts over.
+    ///
+    /// :param audio_data: The audio data to resample
+    /// :type audio_data: :class:`AudioData`
+    ///
+    /// :return: New resampled audio data
+    /// :rtype: :class:`AudioData`
+    fn resample(&mut self, py: Python<'_>, audio_data: &PyAudioData) -> PyResult<PyAudioData> {
+        let samples = audio_data.samples(py)?;
+        let from_rate = audio_data.sample_rate;
+        let num_channels = audio_data.num_channels;
+
+        let same_format = matches!(
+            &self.resampler,
+            Some((_, rate, channels)) if *rate == from_rate && *channels == num_channels
+        );
+
+        if !same_format {
+            // `samples()` already checked the sample rate and channels.
+            let resampler = Resampler::new(from_rate, self.sample_rate, num_channels)
+                .expect("invalid audio data format");
+            self.resampler = Some((resampler, from_rate, num_channels));
+        }
+
+        let (resampler, _, _) = self.resampler.as_mut().unwrap();
+
+        let resampled = py.allow_threads(|| resampler.process(&samples));
+
+        Ok(audio_data.from_samples(py, &resampled, self.sample_rate, num_channels))
+    }
+
+    /// Forgets the audio frames kept from previous calls, e.g. before
+    /// resampling a different stream.
+    fn reset(&mut self) {
+        self.resampler = None;
+    }
+}
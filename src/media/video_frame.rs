@@ -10,7 +10,9 @@ pub struct PyVideoFrame {
     pub width: i32,
     /// The height this frame
     pub height: i32,
-    /// The time in microseconds that the frame was received
+    /// The timestamp in microseconds that WebRTC assigned to the frame. This is
+    /// not the clock used for :attr:`AudioData.timestamp_us`, so they can't
+    /// be compared
     pub timestamp_us: i64,
     /// The frame's color format
     pub color_format: PyObject,
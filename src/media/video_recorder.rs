@@ -0,0 +1,202 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+// Y4M needs a nominal frame rate in its header. Frames are received at
+// whatever rate the remote participant sends them, so the actual timing of
+// each frame is written to the timestamps sidecar file instead.
+const Y4M_FRAME_RATE: &str = "30:1";
+
+// Maximum number of frames waiting to be written. If the disk can't keep up,
+// new frames are dropped instead of blocking the media thread.
+const MAX_PENDING_FRAMES: usize = 30;
+
+struct PendingFrame {
+    data: Vec<u8>,
+    width: i32,
+    height: i32,
+    timestamp_us: i64,
+}
+
+pub(crate) enum RecordingError {
+    // Some frames are being skipped, but the recording continues.
+    FramesSkipped(String),
+    // The recording can't continue.
+    Failed(String),
+}
+
+/// A local recording of a participant's video track. Frames are expected to be
+/// in I420 and are written to a Y4M file. The timestamp of every written frame
+/// is stored in a `<path>.timestamps` sidecar file.
+///
+/// Frames are copied and handed to a writer thread, so `write_frame()` never
+/// blocks on disk I/O.
+pub(crate) struct VideoRecorder {
+    participant_id: String,
+    video_source: String,
+    path: String,
+    sender: SyncSender<PendingFrame>,
+    writer: JoinHandle<io::Result<u64>>,
+    resolution: Option<(i32, i32)>,
+    // The last reported resolution that didn't match the recording.
+    skipped_resolution: Option<(i32, i32)>,
+    // Whether we already reported that the writer can't keep up.
+    writer_behind: bool,
+}
+
+impl VideoRecorder {
+    pub fn new(participant_id: &str, video_source: &str, path: &str) -> io::Result<Self> {
+        let frames = BufWriter::new(File::create(path)?);
+
+        let mut timestamps = BufWriter::new(File::create(format!("{path}.timestamps"))?);
+        writeln!(timestamps, "frame,timestamp_us")?;
+
+        let (sender, receiver) = mpsc::sync_channel(MAX_PENDING_FRAMES);
+
+        let writer = thread::Builder::new()
+            .name("daily-video-recorder".to_string())
+            .spawn(move || write_frames(receiver, frames, timestamps))?;
+
+        Ok(Self {
+            participant_id: participant_id.to_string(),
+            video_source: video_source.to_string(),
+            path: path.to_string(),
+            sender,
+            writer,
+            resolution: None,
+            skipped_resolution: None,
+            writer_behind: false,
+        })
+    }
+
+    pub fn participant_id(&self) -> &str {
+        self.participant_id.as_str()
+    }
+
+    pub fn video_source(&self) -> &str {
+        self.video_source.as_str()
+    }
+
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Queues the given frame to be written. Skipped frames are only reported
+    /// the first time they start being skipped for a given reason.
+    pub fn write_frame(
+        &mut self,
+        buffer: &[u8],
+        width: i32,
+        height: i32,
+        timestamp_us: i64,
+    ) -> Result<(), RecordingError> {
+        match self.resolution {
+            None => self.resolution = Some((width, height)),
+            Some(resolution) if resolution != (width, height) => {
+                // Y4M files have a single resolution, so we can't store frames
+                // after the remote track changes its size.
+                if self.skipped_resolution == Some((width, height)) {
+                    return Ok(());
+                }
+                self.skipped_resolution = Some((width, height));
+                return Err(RecordingError::FramesSkipped(format!(
+                    "skipping {width}x{height} frames, the recording is {}x{}",
+                    resolution.0, resolution.1
+                )));
+            }
+            _ => self.skipped_resolution = None,
+        }
+
+        let chroma_size = ((width as usize + 1) / 2) * ((height as usize + 1) / 2);
+        let frame_size = (width as usize * height as usize) + 2 * chroma_size;
+
+        if buffer.len() < frame_size {
+            return Err(RecordingError::Failed(format!(
+                "invalid I420 frame size {} (expected {frame_size})",
+                buffer.len()
+            )));
+        }
+
+        let frame = PendingFrame {
+            data: buffer[..frame_size].to_vec(),
+            width,
+            height,
+            timestamp_us,
+        };
+
+        match self.sender.try_send(frame) {
+            Ok(()) => {
+                self.writer_behind = false;
+                Ok(())
+            }
+            Err(TrySendError::Full(_)) if self.writer_behind => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.writer_behind = true;
+                Err(RecordingError::FramesSkipped(
+                    "skipping frames, the disk can't keep up".to_string(),
+                ))
+            }
+            // The writer only stops on I/O errors, which are returned by
+            // `finish()`.
+            Err(TrySendError::Disconnected(_)) => Err(RecordingError::Failed(
+                "the recording stopped writing".to_string(),
+            )),
+        }
+    }
+
+    /// Writes all the pending frames and returns the number of frames written.
+    /// This blocks until the writer thread is done.
+    pub fn finish(self) -> io::Result<u64> {
+        drop(self.sender);
+
+        self.writer.join().unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the recording writer panicked",
+            ))
+        })
+    }
+
+    /// Same as `finish()` but doesn't block. The given callback is called with
+    /// the result from another thread once the writer thread is done.
+    pub fn finish_in_background(self, on_finished: impl FnOnce(io::Result<u64>) + Send + 'static) {
+        let result = thread::Builder::new()
+            .name("daily-video-recorder-finish".to_string())
+            .spawn(move || on_finished(self.finish()));
+
+        if let Err(error) = result {
+            tracing::error!("unable to finish local video recording: {error}");
+        }
+    }
+}
+
+fn write_frames(
+    receiver: Receiver<PendingFrame>,
+    mut frames: BufWriter<File>,
+    mut timestamps: BufWriter<File>,
+) -> io::Result<u64> {
+    let mut num_frames = 0;
+
+    for frame in receiver {
+        if num_frames == 0 {
+            writeln!(
+                frames,
+                "YUV4MPEG2 W{} H{} F{Y4M_FRAME_RATE} Ip A1:1 C420jpeg",
+                frame.width, frame.height
+            )?;
+        }
+
+        frames.write_all(b"FRAME\n")?;
+        frames.write_all(&frame.data)?;
+
+        writeln!(timestamps, "{num_frames},{}", frame.timestamp_us)?;
+
+        num_frames += 1;
+    }
+
+    frames.flush()?;
+    timestamps.flush()?;
+
+    Ok(num_frames)
+}
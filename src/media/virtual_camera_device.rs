@@ -1,3 +1,5 @@
+use crate::GLOBAL_CONTEXT;
+
 use webrtc_daily::sys::{
     color_format::ColorFormat, virtual_camera_device::NativeVirtualCameraDevice,
 };
@@ -6,7 +8,7 @@ use daily_core::prelude::daily_core_context_virtual_camera_device_write_frame;
 
 use pyo3::exceptions;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyDict};
 
 /// This class represents a virtual camera device. Virtual camera
 /// devices are used to send video to the meeting.
@@ -33,6 +35,34 @@ impl PyVirtualCameraDevice {
     pub fn attach_camera_device(&mut self, camera_device: NativeVirtualCameraDevice) {
         self.camera_device = Some(camera_device);
     }
+
+    pub fn close_device(&mut self) {
+        // daily-core doesn't have a function to destroy virtual devices.
+        // Dropping the native device releases our reference to it and the
+        // device manager releases its own when the context is destroyed.
+        self.camera_device.take();
+    }
+
+    pub fn forget_device(&mut self) {
+        // The native device belongs to a parent process, so we can't release
+        // it.
+        if let Some(camera_device) = self.camera_device.take() {
+            std::mem::forget(camera_device);
+        }
+    }
+
+    pub fn device_info(&self, py: Python<'_>) -> PyObject {
+        let format = PyDict::new_bound(py);
+        let _ = format.set_item("width", self.width);
+        let _ = format.set_item("height", self.height);
+        let _ = format.set_item("colorFormat", self.color_format.to_string());
+
+        let info = PyDict::new_bound(py);
+        let _ = info.set_item("kind", "camera");
+        let _ = info.set_item("name", self.device_name.as_str());
+        let _ = info.set_item("format", format);
+        info.into_py(py)
+    }
 }
 
 #[pymethods]
@@ -73,6 +103,13 @@ impl PyVirtualCameraDevice {
         self.color_format.to_string()
     }
 
+    /// Closes this virtual camera device and releases its resources. The
+    /// device can't be used after it has been closed.
+    pub fn close(&mut self) {
+        self.close_device();
+        GLOBAL_CONTEXT.unregister_device(&self.device_name);
+    }
+
     /// Writes a video frame to a virtual camera device created with
     /// :func:`Daily.create_camera_device`.
     ///
@@ -81,6 +118,9 @@ impl PyVirtualCameraDevice {
     ///
     /// :param bytestring frame: A bytestring with the video frame contents
     pub fn write_frame(&self, py: Python<'_>, frame: &Bound<'_, PyBytes>) -> PyResult<()> {
+        // Native devices can't be used in a forked child process.
+        GLOBAL_CONTEXT.check_initialized()?;
+
         if let Some(camera_device) = self.camera_device.as_ref() {
             let bytes_length = frame.len()?;
 
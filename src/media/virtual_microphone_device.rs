@@ -1,7 +1,8 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::{collections::HashMap, sync::Mutex};
 
-use crate::util::memory::AlignedI16Data;
+use crate::util::{audio::AudioLevel, memory::AlignedI16Data};
+use crate::GLOBAL_CONTEXT;
 
 use webrtc_daily::sys::virtual_microphone_device::NativeVirtualMicrophoneDevice;
 
@@ -28,6 +29,7 @@ pub struct PyVirtualMicrophoneDevice {
     audio_device: Option<NativeVirtualMicrophoneDevice>,
     request_id: AtomicU64,
     completions: Mutex<HashMap<u64, PyObject>>,
+    level: Mutex<AudioLevel>,
 }
 
 impl PyVirtualMicrophoneDevice {
@@ -39,6 +41,7 @@ impl PyVirtualMicrophoneDevice {
             audio_device: None,
             request_id: AtomicU64::new(0),
             completions: Mutex::new(HashMap::new()),
+            level: Mutex::new(AudioLevel::default()),
         }
     }
 
@@ -46,6 +49,48 @@ impl PyVirtualMicrophoneDevice {
         self.audio_device = Some(audio_device);
     }
 
+    pub fn close_device(&mut self, py: Python<'_>) {
+        // daily-core doesn't have a function to destroy virtual devices.
+        // Dropping the native device releases our reference to it and the
+        // device manager releases its own when the context is destroyed.
+        self.audio_device.take();
+
+        // Pending non-blocking writes will never complete now.
+        let completions: Vec<PyObject> = self
+            .completions
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, completion)| completion)
+            .collect();
+
+        for completion in completions {
+            if let Err(error) = completion.call1(py, (0,)) {
+                error.write_unraisable_bound(py, None);
+            }
+        }
+    }
+
+    pub fn forget_device(&mut self) {
+        // The native device belongs to a parent process, so we can't release
+        // it.
+        if let Some(audio_device) = self.audio_device.take() {
+            std::mem::forget(audio_device);
+        }
+    }
+
+    pub fn device_info(&self, py: Python<'_>) -> PyObject {
+        let format = PyDict::new_bound(py);
+        let _ = format.set_item("sampleRate", self.sample_rate);
+        let _ = format.set_item("channels", self.channels);
+
+        let info = PyDict::new_bound(py);
+        let _ = info.set_item("kind", "microphone");
+        let _ = info.set_item("name", self.device_name.as_str());
+        let _ = info.set_item("format", format);
+        info.into_py(py)
+    }
+
     fn maybe_register_completion(&mut self, completion: Option<PyObject>) -> u64 {
         let request_id = self.request_id.fetch_add(1, Ordering::SeqCst);
 
@@ -89,6 +134,24 @@ impl PyVirtualMicrophoneDevice {
         self.channels
     }
 
+    /// Returns the RMS and peak levels of the last audio frames written to
+    /// this device.
+    ///
+    /// :return: See :ref:`AudioLevel`
+    /// :rtype: dict
+    #[getter]
+    fn level(&self, py: Python<'_>) -> PyObject {
+        self.level.lock().unwrap().to_object(py)
+    }
+
+    /// Closes this virtual microphone device and releases its resources. The
+    /// device can't be used after it has been closed. Completion callbacks of
+    /// pending writes are called with 0 frames written.
+    pub fn close(&mut self, py: Python<'_>) {
+        self.close_device(py);
+        GLOBAL_CONTEXT.unregister_device(&self.device_name);
+    }
+
     /// Writes audio frames to a virtual microphone device created with
     /// :func:`Daily.create_microphone_device`. For non-blocking devices, the
     /// completion callback will be called when the audio frames have been
@@ -108,6 +171,9 @@ impl PyVirtualMicrophoneDevice {
         frames: &Bound<'_, PyBytes>,
         completion: Option<PyObject>,
     ) -> PyResult<PyObject> {
+        // Native devices can't be used in a forked child process.
+        GLOBAL_CONTEXT.check_initialized()?;
+
         if self.audio_device.is_none() {
             return Err(exceptions::PyRuntimeError::new_err(
                 "no microphone device has been attached",
@@ -129,6 +195,10 @@ impl PyVirtualMicrophoneDevice {
         let bytes = frames.as_bytes();
         let aligned = AlignedI16Data::new(bytes);
 
+        let samples =
+            unsafe { std::slice::from_raw_parts(aligned.as_ptr(), num_bytes / bytes_per_sample) };
+        *self.level.lock().unwrap() = AudioLevel::from_samples(samples);
+
         let request_id = self.maybe_register_completion(completion);
 
         Python::with_gil(|py| {
@@ -1,13 +1,16 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::{collections::HashMap, sync::Mutex};
 
+use crate::util::audio::AudioLevel;
+use crate::GLOBAL_CONTEXT;
+
 use webrtc_daily::sys::virtual_speaker_device::NativeVirtualSpeakerDevice;
 
 use daily_core::prelude::daily_core_context_virtual_speaker_device_read_frames;
 
 use pyo3::exceptions;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyTuple};
+use pyo3::types::{PyBytes, PyDict, PyTuple};
 
 /// This class represents a virtual speaker device. Virtual speaker devices are
 /// used to receive audio from the meeting.
@@ -28,6 +31,7 @@ pub struct PyVirtualSpeakerDevice {
     audio_device: Option<NativeVirtualSpeakerDevice>,
     request_id: AtomicU64,
     completions: Mutex<HashMap<u64, PyObject>>,
+    level: Mutex<AudioLevel>,
 }
 
 impl PyVirtualSpeakerDevice {
@@ -40,6 +44,7 @@ impl PyVirtualSpeakerDevice {
             audio_device: None,
             request_id: AtomicU64::new(0),
             completions: Mutex::new(HashMap::new()),
+            level: Mutex::new(AudioLevel::default()),
         }
     }
 
@@ -47,6 +52,51 @@ impl PyVirtualSpeakerDevice {
         self.audio_device = Some(audio_device);
     }
 
+    pub fn close_device(&mut self, py: Python<'_>) {
+        // daily-core doesn't have a function to destroy virtual devices.
+        // Dropping the native device releases our reference to it and the
+        // device manager releases its own when the context is destroyed.
+        self.audio_device.take();
+
+        // Pending non-blocking reads will never complete now.
+        let completions: Vec<PyObject> = self
+            .completions
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, completion)| completion)
+            .collect();
+
+        for completion in completions {
+            let args = PyTuple::new_bound(py, [PyBytes::new_bound(py, &[])]);
+
+            if let Err(error) = completion.call1(py, args) {
+                error.write_unraisable_bound(py, None);
+            }
+        }
+    }
+
+    pub fn forget_device(&mut self) {
+        // The native device belongs to a parent process, so we can't release
+        // it.
+        if let Some(audio_device) = self.audio_device.take() {
+            std::mem::forget(audio_device);
+        }
+    }
+
+    pub fn device_info(&self, py: Python<'_>) -> PyObject {
+        let format = PyDict::new_bound(py);
+        let _ = format.set_item("sampleRate", self.sample_rate);
+        let _ = format.set_item("channels", self.channels);
+        let _ = format.set_item("nonBlocking", self.non_blocking);
+
+        let info = PyDict::new_bound(py);
+        let _ = info.set_item("kind", "speaker");
+        let _ = info.set_item("name", self.device_name.as_str());
+        let _ = info.set_item("format", format);
+        info.into_py(py)
+    }
+
     fn maybe_register_completion(&mut self, completion: Option<PyObject>) -> u64 {
         let request_id = self.request_id.fetch_add(1, Ordering::SeqCst);
 
@@ -59,6 +109,12 @@ impl PyVirtualSpeakerDevice {
 
         request_id
     }
+
+    fn update_level(&self, frames: *const i16, num_frames: usize) {
+        let num_samples = num_frames * self.channels as usize;
+        let samples = unsafe { std::slice::from_raw_parts(frames, num_samples) };
+        *self.level.lock().unwrap() = AudioLevel::from_samples(samples);
+    }
 }
 
 #[pymethods]
@@ -90,6 +146,24 @@ impl PyVirtualSpeakerDevice {
         self.channels
     }
 
+    /// Returns the RMS and peak levels of the last audio frames read from this
+    /// device.
+    ///
+    /// :return: See :ref:`AudioLevel`
+    /// :rtype: dict
+    #[getter]
+    fn level(&self, py: Python<'_>) -> PyObject {
+        self.level.lock().unwrap().to_object(py)
+    }
+
+    /// Closes this virtual speaker device and releases its resources. The
+    /// device can't be used after it has been closed. Completion callbacks of
+    /// pending reads are called with an empty bytestring.
+    pub fn close(&mut self, py: Python<'_>) {
+        self.close_device(py);
+        GLOBAL_CONTEXT.unregister_device(&self.device_name);
+    }
+
     /// Reads audio frames from a virtual speaker device created with
     /// :func:`Daily.create_speaker_device`. For non-blocking devices, the
     /// completion callback will be called when the audio frames have been read.
@@ -105,6 +179,9 @@ impl PyVirtualSpeakerDevice {
         num_frames: usize,
         completion: Option<PyObject>,
     ) -> PyResult<PyObject> {
+        // Native devices can't be used in a forked child process.
+        GLOBAL_CONTEXT.check_initialized()?;
+
         if self.audio_device.is_none() {
             return Err(exceptions::PyRuntimeError::new_err(
                 "no speaker device has been attached",
@@ -142,6 +219,10 @@ impl PyVirtualSpeakerDevice {
             });
 
             if frames_read == num_frames as i32 {
+                if num_bytes > 0 {
+                    self.update_level(buffer.as_ptr(), num_frames);
+                }
+
                 let py_bytes =
                     unsafe { PyBytes::bound_from_ptr(py, buffer.as_ptr() as *const u8, num_bytes) };
                 Ok(py_bytes.into_py(py))
@@ -167,6 +248,10 @@ pub(crate) unsafe extern "C" fn on_read_frames(
     let speaker: &mut PyVirtualSpeakerDevice =
         unsafe { &mut *(device as *mut PyVirtualSpeakerDevice) };
 
+    if num_frames > 0 && !frames.is_null() {
+        speaker.update_level(frames, num_frames);
+    }
+
     Python::with_gil(|py| {
         let completion = speaker.completions.lock().unwrap().remove(&request_id);
 
@@ -0,0 +1,335 @@
+pub(crate) mod server;
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde_json::Value;
+
+// Upper bounds (in seconds) of the request duration histogram buckets.
+const REQUEST_DURATION_BUCKETS: [f64; 9] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// The values we export from a `network-stats-updated` event. Values are
+/// optional because not all of them are always available.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NetworkMetrics {
+    pub receive_bits_per_second: Option<f64>,
+    pub send_bits_per_second: Option<f64>,
+    pub receive_packet_loss: Option<f64>,
+    pub send_packet_loss: Option<f64>,
+    pub round_trip_time: Option<f64>,
+    pub quality: Option<f64>,
+}
+
+impl NetworkMetrics {
+    pub fn from_stats(stats: &Value) -> Self {
+        let latest = stats.pointer("/stats/latest");
+        let latest_value = |key: &str| {
+            latest
+                .and_then(|latest| latest.get(key))
+                .and_then(Value::as_f64)
+        };
+
+        Self {
+            receive_bits_per_second: latest_value("receiveBitsPerSecond"),
+            send_bits_per_second: latest_value("sendBitsPerSecond"),
+            receive_packet_loss: latest_value("totalRecvPacketLoss"),
+            send_packet_loss: latest_value("totalSendPacketLoss"),
+            round_trip_time: latest_value("networkRoundTripTime"),
+            quality: stats.get("quality").and_then(Value::as_f64),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Histogram {
+    // Cumulative counts, one for each bucket in REQUEST_DURATION_BUCKETS.
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; REQUEST_DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(REQUEST_DURATION_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct CallClientMetricsState {
+    network: NetworkMetrics,
+    participants_present: Option<f64>,
+    participants_hidden: Option<f64>,
+    pending_requests: HashMap<u64, Instant>,
+    request_durations: Histogram,
+    request_errors: u64,
+}
+
+/// Metrics of a single call client. Frame counters are atomic since they are
+/// updated from the media threads for every frame.
+#[derive(Default)]
+pub(crate) struct CallClientMetrics {
+    video_frames: AtomicU64,
+    audio_frames: AtomicU64,
+    state: Mutex<CallClientMetricsState>,
+}
+
+impl CallClientMetrics {
+    pub fn add_video_frame(&self) {
+        self.video_frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_audio_frames(&self, num_frames: u64) {
+        self.audio_frames.fetch_add(num_frames, Ordering::Relaxed);
+    }
+
+    pub fn update_network_stats(&self, network: NetworkMetrics) {
+        self.state.lock().unwrap().network = network;
+    }
+
+    pub fn update_participant_counts(&self, counts: &Value) {
+        let mut state = self.state.lock().unwrap();
+        state.participants_present = counts.get("present").and_then(Value::as_f64);
+        state.participants_hidden = counts.get("hidden").and_then(Value::as_f64);
+    }
+
+    pub fn request_started(&self, request_id: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .pending_requests
+            .insert(request_id, Instant::now());
+    }
+
+    pub fn request_completed(&self, request_id: u64, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(started) = state.pending_requests.remove(&request_id) {
+            state
+                .request_durations
+                .observe(started.elapsed().as_secs_f64());
+            if !success {
+                state.request_errors += 1;
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> CallClientMetricsSnapshot {
+        let state = self.state.lock().unwrap();
+        CallClientMetricsSnapshot {
+            video_frames: self.video_frames.load(Ordering::Relaxed),
+            audio_frames: self.audio_frames.load(Ordering::Relaxed),
+            network: state.network.clone(),
+            participants_present: state.participants_present,
+            participants_hidden: state.participants_hidden,
+            request_durations: state.request_durations.clone(),
+            request_errors: state.request_errors,
+        }
+    }
+}
+
+pub(crate) struct CallClientMetricsSnapshot {
+    video_frames: u64,
+    audio_frames: u64,
+    network: NetworkMetrics,
+    participants_present: Option<f64>,
+    participants_hidden: Option<f64>,
+    request_durations: Histogram,
+    request_errors: u64,
+}
+
+/// A call client identified by its ID and the meeting URL it last joined.
+pub(crate) struct CallClientMetricsEntry {
+    pub client_id: u64,
+    pub meeting_url: Option<String>,
+    pub metrics: CallClientMetricsSnapshot,
+}
+
+impl CallClientMetricsEntry {
+    fn labels(&self) -> String {
+        format!(
+            "client_id=\"{}\",meeting_url=\"{}\"",
+            self.client_id,
+            escape_label_value(self.meeting_url.as_deref().unwrap_or(""))
+        )
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_client_family(
+    out: &mut String,
+    entries: &[CallClientMetricsEntry],
+    name: &str,
+    kind: &str,
+    help: &str,
+    value: impl Fn(&CallClientMetricsSnapshot) -> Option<f64>,
+) {
+    write_header(out, name, kind, help);
+    for entry in entries {
+        if let Some(value) = value(&entry.metrics) {
+            let _ = writeln!(out, "{name}{{{}}} {value}", entry.labels());
+        }
+    }
+}
+
+/// Renders the given metrics in the Prometheus text exposition format.
+pub(crate) fn render_metrics(entries: &[CallClientMetricsEntry], num_devices: usize) -> String {
+    let mut out = String::new();
+
+    write_header(
+        &mut out,
+        "daily_call_clients",
+        "gauge",
+        "Number of call clients that have not been released.",
+    );
+    let _ = writeln!(out, "daily_call_clients {}", entries.len());
+
+    write_header(
+        &mut out,
+        "daily_virtual_devices",
+        "gauge",
+        "Number of virtual devices that have not been destroyed.",
+    );
+    let _ = writeln!(out, "daily_virtual_devices {num_devices}");
+
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_video_frames_received_total",
+        "counter",
+        "Video frames received by the call client renderers.",
+        |m| Some(m.video_frames as f64),
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_audio_frames_received_total",
+        "counter",
+        "Audio frames (samples per channel) received by the call client renderers.",
+        |m| Some(m.audio_frames as f64),
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_receive_bits_per_second",
+        "gauge",
+        "Latest receive bitrate.",
+        |m| m.network.receive_bits_per_second,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_send_bits_per_second",
+        "gauge",
+        "Latest send bitrate.",
+        |m| m.network.send_bits_per_second,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_receive_packet_loss",
+        "gauge",
+        "Latest receive packet loss.",
+        |m| m.network.receive_packet_loss,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_send_packet_loss",
+        "gauge",
+        "Latest send packet loss.",
+        |m| m.network.send_packet_loss,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_round_trip_time_seconds",
+        "gauge",
+        "Latest network round-trip time.",
+        |m| m.network.round_trip_time,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_network_quality",
+        "gauge",
+        "Latest network quality (0-100).",
+        |m| m.network.quality,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_participants_present",
+        "gauge",
+        "Number of present participants in the meeting.",
+        |m| m.participants_present,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_participants_hidden",
+        "gauge",
+        "Number of hidden participants in the meeting.",
+        |m| m.participants_hidden,
+    );
+    write_client_family(
+        &mut out,
+        entries,
+        "daily_request_errors_total",
+        "counter",
+        "Requests with a completion callback that failed.",
+        |m| Some(m.request_errors as f64),
+    );
+
+    let name = "daily_request_duration_seconds";
+    write_header(
+        &mut out,
+        name,
+        "histogram",
+        "Time until requests with a completion callback are completed.",
+    );
+    for entry in entries {
+        let labels = entry.labels();
+        let histogram = &entry.metrics.request_durations;
+        for (count, bound) in histogram.buckets.iter().zip(REQUEST_DURATION_BUCKETS) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{labels},le=\"+Inf\"}} {}",
+            histogram.count
+        );
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", histogram.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", histogram.count);
+    }
+
+    out
+}
@@ -0,0 +1,188 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, RawFd};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::GLOBAL_CONTEXT;
+
+// Don't let a slow client block the server forever.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+// How often the server checks whether it needs to stop.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+
+// Limits for the request line and the headers, so a client can't make us
+// buffer an unbounded amount of data.
+const MAX_LINE_SIZE: u64 = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+
+/// A minimal HTTP server that serves the metrics (in the Prometheus text
+/// format) on `/metrics`. Connections are handled one at a time in a
+/// background thread until the server is stopped.
+pub(crate) struct MetricsServer {
+    port: u16,
+    // The ID of the process that started the server.
+    pid: u32,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+    #[cfg(unix)]
+    listener_fd: RawFd,
+}
+
+impl MetricsServer {
+    pub fn start(host: &str, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((host, port))?;
+
+        // Don't block on accept() so we can check if we need to stop.
+        listener.set_nonblocking(true)?;
+
+        let port = listener.local_addr()?.port();
+
+        #[cfg(unix)]
+        let listener_fd = listener.as_raw_fd();
+
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = stop.clone();
+        let thread = thread::Builder::new()
+            .name("daily-metrics".to_string())
+            .spawn(move || serve(listener, &thread_stop))?;
+
+        Ok(Self {
+            port,
+            pid: process::id(),
+            stop,
+            thread,
+            #[cfg(unix)]
+            listener_fd,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Whether the server was started by a parent process, in which case the
+    /// server thread doesn't exist in this process.
+    pub fn is_from_parent(&self) -> bool {
+        self.pid != process::id()
+    }
+
+    /// Stops the server and waits for the server thread to finish. This might
+    /// wait for a connection being served.
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+
+        if self.thread.join().is_err() {
+            tracing::error!("metrics server thread panicked");
+        }
+    }
+
+    /// Drops a server started by a parent process. The listener belongs to a
+    /// thread that doesn't exist in this process, so we just close our copy
+    /// of the socket.
+    pub fn forget(self) {
+        #[cfg(unix)]
+        unsafe {
+            libc::close(self.listener_fd);
+        }
+
+        std::mem::forget(self.thread);
+    }
+}
+
+fn serve(listener: TcpListener, stop: &AtomicBool) {
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(error) = handle_connection(stream) {
+                    tracing::debug!("unable to serve metrics: {error}");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+            Err(error) => {
+                tracing::debug!("unable to accept metrics connection: {error}");
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+        }
+    }
+}
+
+// Reads a line, failing if it's longer than MAX_LINE_SIZE.
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+
+    reader.take(MAX_LINE_SIZE).read_line(&mut line)?;
+
+    if line.len() as u64 == MAX_LINE_SIZE && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request line or header too long",
+        ));
+    }
+
+    Ok(line)
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    // Accepted sockets might inherit the non-blocking mode of the listener.
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let request_line = read_line(&mut reader)?;
+
+    // We don't need any of the headers.
+    let mut num_headers = 0;
+    loop {
+        let header = read_line(&mut reader)?;
+        if header.is_empty() || header.trim().is_empty() {
+            break;
+        }
+
+        num_headers += 1;
+        if num_headers > MAX_HEADERS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "too many request headers",
+            ));
+        }
+    }
+
+    let mut request = request_line.split_whitespace();
+    let method = request.next().unwrap_or_default();
+    let path = request
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, body) = if method == "GET" && (path == "/metrics" || path == "/") {
+        ("200 OK", GLOBAL_CONTEXT.metrics_text())
+    } else {
+        ("404 Not Found", String::new())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}",
+        body.len()
+    )?;
+
+    stream.flush()
+}
@@ -1,2 +1,7 @@
+pub(crate) mod audio;
+pub(crate) mod base64;
+pub(crate) mod checksum;
 pub(crate) mod dict;
 pub(crate) mod memory;
+pub(crate) mod time;
+pub(crate) mod timer;
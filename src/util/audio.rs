@@ -0,0 +1,421 @@
+// Helpers to work with interleaved 16-bit linear PCM samples.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+pub(crate) fn samples_from_bytes(bytes: &[u8]) -> Vec<i16> {
+    bytes
+        .chunks_exact(2)
+        .map(|b| i16::from_ne_bytes([b[0], b[1]]))
+        .collect()
+}
+
+pub(crate) fn samples_to_bytes(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_ne_bytes()).collect()
+}
+
+pub(crate) fn samples_to_f32(samples: &[i16]) -> Vec<f32> {
+    samples
+        .iter()
+        .map(|s| *s as f32 / -(i16::MIN as f32))
+        .collect()
+}
+
+pub(crate) fn f32_to_bytes(samples: &[f32]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_ne_bytes()).collect()
+}
+
+pub(crate) fn downmix_to_mono(samples: &[i16], num_channels: usize) -> Vec<i16> {
+    if num_channels <= 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks_exact(num_channels)
+        .map(|frame| {
+            let sum: i32 = frame.iter().map(|s| *s as i32).sum();
+            (sum / num_channels as i32) as i16
+        })
+        .collect()
+}
+
+// Number of zero crossings of the resampling filter on each side. More zero
+// crossings give a sharper cutoff at the cost of more computation.
+const RESAMPLER_ZERO_CROSSINGS: f64 = 16.0;
+
+// The filter cutoff as a fraction of the lowest Nyquist frequency, which
+// leaves room for the transition band so there's no aliasing.
+const RESAMPLER_ROLLOFF: f64 = 0.9;
+
+// Filter coefficients are computed once per phase if there are at most this
+// many phases, otherwise they are computed for every output frame.
+const RESAMPLER_MAX_CACHED_PHASES: u64 = 1024;
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// A windowed-sinc (Blackman) resampler for interleaved 16-bit linear PCM.
+/// The filter low-passes the signal below the lowest of the two Nyquist
+/// frequencies, so downsampling doesn't alias.
+///
+/// The resampler keeps the last input frames between calls to `process()`, so
+/// consecutive chunks of a stream are resampled without discontinuities. The
+/// output is delayed by half the filter length (less than 2 milliseconds for
+/// the usual sample rates) until `flush()` is called.
+pub(crate) struct Resampler {
+    num_channels: usize,
+    // The output rate is `from_rate * up / down`.
+    up: u64,
+    down: u64,
+    // Normalized cutoff frequency (1.0 is the input Nyquist frequency).
+    cutoff: f64,
+    // Number of filter taps on each side of the output frame.
+    half_taps: usize,
+    phases: Vec<Vec<f32>>,
+    // Interleaved input frames that are still needed by the filter.
+    buffer: Vec<i16>,
+    // Position of the next output frame in the buffer, in 1/up input frames.
+    position: u64,
+    num_input_frames: u64,
+    num_output_frames: u64,
+}
+
+impl Resampler {
+    /// Returns `None` if any of the sample rates or the number of channels is
+    /// 0.
+    pub fn new(from_rate: u32, to_rate: u32, num_channels: usize) -> Option<Self> {
+        if from_rate == 0 || to_rate == 0 || num_channels == 0 {
+            return None;
+        }
+
+        let divisor = gcd(from_rate as u64, to_rate as u64);
+        let up = to_rate as u64 / divisor;
+        let down = from_rate as u64 / divisor;
+
+        let cutoff = (up as f64 / down as f64).min(1.0) * RESAMPLER_ROLLOFF;
+        let half_taps = (RESAMPLER_ZERO_CROSSINGS / cutoff).ceil() as usize;
+
+        let mut resampler = Self {
+            num_channels,
+            up,
+            down,
+            cutoff,
+            half_taps,
+            phases: Vec::new(),
+            // Start with silence so the first output frame is centered on the
+            // first input frame.
+            buffer: vec![0; half_taps * num_channels],
+            position: half_taps as u64 * up,
+            num_input_frames: 0,
+            num_output_frames: 0,
+        };
+
+        if up <= RESAMPLER_MAX_CACHED_PHASES {
+            resampler.phases = (0..up).map(|phase| resampler.weights(phase)).collect();
+        }
+
+        Some(resampler)
+    }
+
+    /// Resamples the given interleaved samples. The output only contains the
+    /// frames for which the filter has enough input.
+    pub fn process(&mut self, samples: &[i16]) -> Vec<i16> {
+        let num_frames = samples.len() / self.num_channels;
+
+        self.buffer
+            .extend_from_slice(&samples[..num_frames * self.num_channels]);
+        self.num_input_frames += num_frames as u64;
+
+        self.resample_buffer(u64::MAX)
+    }
+
+    /// Returns the remaining output frames, as if the input was followed by
+    /// silence. The resampler can't be used after this.
+    pub fn flush(&mut self) -> Vec<i16> {
+        let padded_len = self.buffer.len() + self.half_taps * self.num_channels;
+        self.buffer.resize(padded_len, 0);
+
+        // The number of output frames that fall within the input.
+        let total_output_frames = (self.num_input_frames * self.up).div_ceil(self.down);
+
+        self.resample_buffer(total_output_frames)
+    }
+
+    fn resample_buffer(&mut self, max_output_frames: u64) -> Vec<i16> {
+        let num_frames = self.buffer.len() / self.num_channels;
+
+        let mut output = Vec::new();
+
+        let mut on_the_fly_weights;
+
+        while self.num_output_frames < max_output_frames {
+            let index = (self.position / self.up) as usize;
+            let phase = self.position % self.up;
+
+            // The filter needs `half_taps` frames after the current one.
+            if index + self.half_taps >= num_frames {
+                break;
+            }
+
+            let weights = if self.phases.is_empty() {
+                on_the_fly_weights = self.weights(phase);
+                &on_the_fly_weights
+            } else {
+                &self.phases[phase as usize]
+            };
+
+            let first_frame = index + 1 - self.half_taps;
+
+            for channel in 0..self.num_channels {
+                let value: f32 = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(tap, weight)| {
+                        weight
+                            * self.buffer[(first_frame + tap) * self.num_channels + channel] as f32
+                    })
+                    .sum();
+
+                output.push(value.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+            }
+
+            self.position += self.down;
+            self.num_output_frames += 1;
+        }
+
+        // Drop the frames that are not needed anymore.
+        let first_needed = ((self.position / self.up) as usize + 1)
+            .saturating_sub(self.half_taps)
+            .min(num_frames);
+
+        self.buffer.drain(..first_needed * self.num_channels);
+        self.position -= first_needed as u64 * self.up;
+
+        output
+    }
+
+    // The filter coefficients for the given phase. Coefficient `tap` is
+    // applied to the input frame at `tap + 1 - half_taps` frames from the
+    // current one.
+    fn weights(&self, phase: u64) -> Vec<f32> {
+        let fraction = phase as f64 / self.up as f64;
+        let half_width = self.half_taps as f64;
+
+        let weights: Vec<f64> = (0..2 * self.half_taps)
+            .map(|tap| {
+                let distance = (tap as f64 + 1.0 - half_width) - fraction;
+                let x = distance / half_width;
+                if x.abs() >= 1.0 {
+                    return 0.0;
+                }
+
+                let window = 0.42
+                    + 0.5 * (std::f64::consts::PI * x).cos()
+                    + 0.08 * (2.0 * std::f64::consts::PI * x).cos();
+
+                let t = std::f64::consts::PI * self.cutoff * distance;
+                let sinc = if t == 0.0 { 1.0 } else { t.sin() / t };
+
+                sinc * window
+            })
+            .collect();
+
+        // Normalize so there's no gain at 0 Hz.
+        let sum: f64 = weights.iter().sum();
+
+        weights.iter().map(|weight| (weight / sum) as f32).collect()
+    }
+}
+
+/// Resamples a complete block of interleaved samples.
+pub(crate) fn resample(
+    samples: &[i16],
+    num_channels: usize,
+    from_rate: u32,
+    to_rate: u32,
+) -> Vec<i16> {
+    if from_rate == to_rate {
+        return samples.to_vec();
+    }
+
+    let Some(mut resampler) = Resampler::new(from_rate, to_rate, num_channels) else {
+        return Vec::new();
+    };
+
+    let mut output = resampler.process(samples);
+    output.extend(resampler.flush());
+    output
+}
+
+/// RMS and peak levels of a block of audio samples, normalized to [0.0, 1.0].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct AudioLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+impl AudioLevel {
+    pub fn from_samples(samples: &[i16]) -> Self {
+        let mut meter = AudioLevelMeter::default();
+        meter.add_samples(samples);
+        meter.level()
+    }
+}
+
+fn level_to_dbfs(level: f32) -> f32 {
+    if level > 0.0 {
+        20.0 * level.log10()
+    } else {
+        f32::NEG_INFINITY
+    }
+}
+
+impl ToPyObject for AudioLevel {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        let level = PyDict::new_bound(py);
+        let _ = level.set_item("rms", self.rms);
+        let _ = level.set_item("peak", self.peak);
+        let _ = level.set_item("rmsDbfs", level_to_dbfs(self.rms));
+        let _ = level.set_item("peakDbfs", level_to_dbfs(self.peak));
+        level.into_py(py)
+    }
+}
+
+/// Accumulates audio samples so levels can be computed over multiple blocks
+/// of audio.
+#[derive(Debug, Default)]
+pub(crate) struct AudioLevelMeter {
+    sum_squares: f64,
+    peak: u16,
+    num_samples: u64,
+}
+
+impl AudioLevelMeter {
+    pub fn add_samples(&mut self, samples: &[i16]) {
+        for sample in samples {
+            self.sum_squares += (*sample as f64) * (*sample as f64);
+            self.peak = self.peak.max(sample.unsigned_abs());
+        }
+        self.num_samples += samples.len() as u64;
+    }
+
+    pub fn level(&self) -> AudioLevel {
+        if self.num_samples == 0 {
+            return AudioLevel::default();
+        }
+
+        let full_scale = -(i16::MIN as f64);
+        let rms = (self.sum_squares / self.num_samples as f64).sqrt() / full_scale;
+
+        AudioLevel {
+            rms: rms.min(1.0) as f32,
+            peak: (self.peak as f64 / full_scale) as f32,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f64, sample_rate: u32, num_frames: usize) -> Vec<i16> {
+        (0..num_frames)
+            .map(|i| {
+                let t = i as f64 / sample_rate as f64;
+                (16384.0 * (2.0 * std::f64::consts::PI * frequency * t).sin()) as i16
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[i16]) -> f64 {
+        let sum: f64 = samples.iter().map(|s| (*s as f64) * (*s as f64)).sum();
+        (sum / samples.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn resample_output_length() {
+        assert_eq!(resample(&vec![0; 480], 1, 48000, 16000).len(), 160);
+        assert_eq!(resample(&vec![0; 160], 1, 16000, 48000).len(), 480);
+        assert_eq!(resample(&vec![0; 882], 2, 44100, 16000).len(), 160 * 2);
+        assert_eq!(resample(&vec![0; 10], 1, 16000, 16000).len(), 10);
+    }
+
+    #[test]
+    fn resample_keeps_passband() {
+        let input = sine(1000.0, 48000, 4800);
+        let output = resample(&input, 1, 48000, 16000);
+
+        // Skip the edges, where the filter sees the silence around the input.
+        let ratio = rms(&output[100..1500]) / rms(&input[300..4500]);
+        assert!((ratio - 1.0).abs() < 0.01, "ratio {ratio}");
+    }
+
+    #[test]
+    fn resample_filters_aliases() {
+        // 10 kHz is above the 8 kHz Nyquist frequency of 16 kHz audio and
+        // would fold back to 6 kHz without filtering.
+        let input = sine(10000.0, 48000, 4800);
+        let output = resample(&input, 1, 48000, 16000);
+
+        let ratio = rms(&output[100..1500]) / rms(&input[300..4500]);
+        assert!(ratio < 0.01, "ratio {ratio}");
+    }
+
+    #[test]
+    fn resample_keeps_channels_apart() {
+        let left = sine(500.0, 48000, 960);
+        let stereo: Vec<i16> = left.iter().flat_map(|s| [*s, 0]).collect();
+
+        let output = resample(&stereo, 2, 48000, 16000);
+
+        assert_eq!(output.len(), 320 * 2);
+        assert!(output.iter().skip(1).step_by(2).all(|s| *s == 0));
+        assert!(rms(&output.iter().step_by(2).copied().collect::<Vec<_>>()) > 1000.0);
+    }
+
+    #[test]
+    fn resampler_chunks_match_single_block() {
+        for (from_rate, to_rate) in [
+            (48000, 16000),
+            (16000, 48000),
+            (44100, 16000),
+            (8000, 11025),
+        ] {
+            let input = sine(440.0, from_rate, from_rate as usize / 10);
+
+            let expected = resample(&input, 1, from_rate, to_rate);
+
+            let mut resampler = Resampler::new(from_rate, to_rate, 1).unwrap();
+            let mut output = Vec::new();
+            for chunk in input.chunks(37) {
+                output.extend(resampler.process(chunk));
+            }
+            output.extend(resampler.flush());
+
+            assert_eq!(output, expected, "{from_rate} -> {to_rate}");
+        }
+    }
+
+    #[test]
+    fn resampler_rejects_zero() {
+        assert!(Resampler::new(0, 16000, 1).is_none());
+        assert!(Resampler::new(16000, 0, 1).is_none());
+        assert!(Resampler::new(16000, 48000, 0).is_none());
+        assert!(resample(&[1, 2, 3], 1, 0, 16000).is_empty());
+    }
+
+    #[test]
+    fn downmix_averages_channels() {
+        assert_eq!(downmix_to_mono(&[100, 200, -50, 50], 2), vec![150, 0]);
+        assert_eq!(downmix_to_mono(&[1, 2, 3], 1), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,135 @@
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the given data as standard (padded) base64.
+pub(crate) fn encode(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        result.push(ALPHABET[(triple >> 18) as usize & 0x3F] as char);
+        result.push(ALPHABET[(triple >> 12) as usize & 0x3F] as char);
+        if chunk.len() > 1 {
+            result.push(ALPHABET[(triple >> 6) as usize & 0x3F] as char);
+        } else {
+            result.push('=');
+        }
+        if chunk.len() > 2 {
+            result.push(ALPHABET[triple as usize & 0x3F] as char);
+        } else {
+            result.push('=');
+        }
+    }
+
+    result
+}
+
+fn decode_char(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some((c - b'A') as u32),
+        b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+        b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes standard (padded) base64. Returns `None` if the input is not valid
+/// base64.
+pub(crate) fn decode(data: &str) -> Option<Vec<u8>> {
+    let data = data.as_bytes();
+
+    if data.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(data.len() / 4 * 3);
+
+    let num_chunks = data.len() / 4;
+    for (i, chunk) in data.chunks(4).enumerate() {
+        // Padding is only allowed at the end.
+        let padding = if i + 1 == num_chunks {
+            chunk.iter().rev().take_while(|c| **c == b'=').count()
+        } else {
+            0
+        };
+        if padding > 2 {
+            return None;
+        }
+
+        let mut triple = 0;
+        for c in &chunk[..4 - padding] {
+            triple = (triple << 6) | decode_char(*c)?;
+        }
+        triple <<= 6 * padding;
+
+        result.push((triple >> 16) as u8);
+        if padding < 2 {
+            result.push((triple >> 8) as u8);
+        }
+        if padding < 1 {
+            result.push(triple as u8);
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_known_values() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode(&[0xFB, 0xFF]), "+/8=");
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..data.len() {
+            assert_eq!(decode(&encode(&data[..len])).as_deref(), Some(&data[..len]));
+        }
+    }
+
+    #[test]
+    fn padding_in_the_middle() {
+        assert_eq!(decode("Zg==Zm9v"), None);
+        assert_eq!(decode("Zm8=Zm9v"), None);
+        assert_eq!(decode("Z=9v"), None);
+        assert_eq!(decode("Zm=v"), None);
+    }
+
+    #[test]
+    fn too_much_padding() {
+        assert_eq!(decode("Z==="), None);
+        assert_eq!(decode("===="), None);
+    }
+
+    #[test]
+    fn wrong_length() {
+        assert_eq!(decode("Z"), None);
+        assert_eq!(decode("Zm9"), None);
+        assert_eq!(decode("Zm9vY"), None);
+        assert_eq!(decode("Zg="), None);
+    }
+
+    #[test]
+    fn non_alphabet_bytes() {
+        assert_eq!(decode("Zm9v!mFy"), None);
+        assert_eq!(decode("Zm9-"), None);
+        assert_eq!(decode("Zm9_"), None);
+        assert_eq!(decode("Zm 9"), None);
+        assert_eq!(decode("Zm9\n"), None);
+        assert_eq!(decode("Zmé="), None);
+    }
+}
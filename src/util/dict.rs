@@ -1,18 +1,40 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
-use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyLong, PyString};
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyList, PyLong, PyMemoryView, PyString,
+};
+
+use crate::util::base64;
+
+// The key of the objects that represent binary data (encoded as base64).
+const BINARY_KEY: &str = "dailyBinary";
 
 #[repr(transparent)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct DictValue(pub Value);
 
 impl DictValue {
-    fn value_to_object(val: &Value, py: Python<'_>) -> PyObject {
+    /// Same as `extract()` but `bytes`, `bytearray` and `memoryview` values
+    /// are also accepted. They are encoded as `{"dailyBinary": "<base64>"}`.
+    pub fn extract_with_binary(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Self::object_to_value(ob, true).map(DictValue)
+    }
+
+    /// Same as `to_object()` but values encoded by `extract_with_binary()`
+    /// are decoded back to `bytes`. Note that any object with `"dailyBinary"`
+    /// as its only key and valid base64 as its value is decoded, including
+    /// the ones sent by other clients (e.g. web clients) that didn't mean to
+    /// send binary data. Objects with invalid base64 are left as they are.
+    pub fn to_object_with_binary(&self, py: Python<'_>) -> PyObject {
+        Self::value_to_object(&self.0, py, true)
+    }
+
+    fn value_to_object(val: &Value, py: Python<'_>, binary: bool) -> PyObject {
         match val {
             Value::Null => py.None(),
             Value::Bool(b) => b.to_object(py),
@@ -24,48 +46,97 @@ impl DictValue {
                 .expect("Invalid number"),
             Value::String(s) => s.to_object(py),
             Value::Array(v) => {
-                let inner: Vec<_> = v.iter().map(|x| Self::value_to_object(x, py)).collect();
+                let inner: Vec<_> = v
+                    .iter()
+                    .map(|x| Self::value_to_object(x, py, binary))
+                    .collect();
                 inner.to_object(py)
             }
             Value::Object(m) => {
+                if binary {
+                    if let Some(data) = Self::binary_from_map(m) {
+                        return PyBytes::new_bound(py, &data).into_any().unbind();
+                    }
+                }
+
                 let inner: HashMap<_, _> = m
                     .iter()
-                    .map(|(k, v)| (k, Self::value_to_object(v, py)))
+                    .map(|(k, v)| (k, Self::value_to_object(v, py, binary)))
                     .collect();
                 inner.to_object(py)
             }
         }
     }
-}
 
-impl ToPyObject for DictValue {
-    fn to_object(&self, py: Python<'_>) -> PyObject {
-        Self::value_to_object(&self.0, py)
+    fn binary_from_map(map: &Map<String, Value>) -> Option<Vec<u8>> {
+        if map.len() != 1 {
+            return None;
+        }
+
+        map.get(BINARY_KEY)
+            .and_then(Value::as_str)
+            .and_then(base64::decode)
     }
-}
 
-impl<'a> FromPyObject<'a> for DictValue {
-    fn extract(ob: &'a PyAny) -> Result<Self, PyErr> {
+    fn binary_to_value(data: &[u8]) -> Value {
+        let mut map = Map::new();
+        map.insert(BINARY_KEY.to_string(), base64::encode(data).into());
+        Value::Object(map)
+    }
+
+    fn object_to_value(ob: &Bound<'_, PyAny>, binary: bool) -> PyResult<Value> {
         if let Ok(value) = ob.downcast::<PyBool>() {
-            Ok(DictValue(value.is_true().into()))
+            Ok(value.is_true().into())
         } else if let Ok(value) = ob.downcast::<PyLong>() {
-            let number: i64 = value.extract().unwrap();
-            Ok(DictValue(number.into()))
+            let number: i64 = value.extract()?;
+            Ok(number.into())
         } else if let Ok(value) = ob.downcast::<PyFloat>() {
-            let number: f64 = value.extract().unwrap();
-            Ok(DictValue(number.into()))
+            let number: f64 = value.extract()?;
+            Ok(number.into())
         } else if let Ok(value) = ob.downcast::<PyString>() {
-            Ok(DictValue(value.to_string().into()))
+            Ok(value.to_string().into())
         } else if let Ok(value) = ob.downcast::<PyList>() {
-            let list: Vec<DictValue> = value.extract().unwrap();
-            let vec = list.iter().map(|v| v.0.clone()).collect();
-            Ok(DictValue(Value::Array(vec)))
+            let vec = value
+                .iter()
+                .map(|v| Self::object_to_value(&v, binary))
+                .collect::<PyResult<_>>()?;
+            Ok(Value::Array(vec))
         } else if let Ok(value) = ob.downcast::<PyDict>() {
-            let dict: HashMap<String, DictValue> = value.extract().unwrap();
-            let map = dict.iter().map(|(k, v)| (k.clone(), v.0.clone())).collect();
-            Ok(DictValue(Value::Object(map)))
+            let map = value
+                .iter()
+                .map(|(k, v)| Ok((k.extract::<String>()?, Self::object_to_value(&v, binary)?)))
+                .collect::<PyResult<_>>()?;
+            Ok(Value::Object(map))
+        } else if let Some(data) = Self::object_to_binary(ob, binary)? {
+            Ok(Self::binary_to_value(&data))
         } else {
             Err(PyErr::new::<PyTypeError, _>("Invalid dictionary"))
         }
     }
+
+    fn object_to_binary(ob: &Bound<'_, PyAny>, binary: bool) -> PyResult<Option<Vec<u8>>> {
+        if !binary {
+            Ok(None)
+        } else if let Ok(value) = ob.downcast::<PyBytes>() {
+            Ok(Some(value.as_bytes().to_vec()))
+        } else if let Ok(value) = ob.downcast::<PyByteArray>() {
+            Ok(Some(value.to_vec()))
+        } else if let Ok(value) = ob.downcast::<PyMemoryView>() {
+            Ok(Some(value.call_method0("tobytes")?.extract()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ToPyObject for DictValue {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        Self::value_to_object(&self.0, py, false)
+    }
+}
+
+impl<'a> FromPyObject<'a> for DictValue {
+    fn extract(ob: &'a PyAny) -> Result<Self, PyErr> {
+        Self::object_to_value(&ob.as_borrowed(), false).map(DictValue)
+    }
 }
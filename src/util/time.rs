@@ -0,0 +1,145 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current system time in seconds since the UNIX epoch.
+pub(crate) fn system_time_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |t| t.as_secs_f64())
+}
+
+/// Returns the current time of the system's monotonic clock in microseconds.
+pub(crate) fn monotonic_time_us() -> i64 {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+    }
+
+    (ts.tv_sec as i64 * 1_000_000) + (ts.tv_nsec as i64 / 1_000)
+}
+
+/// Parses an RFC 3339 (ISO 8601) date and time, e.g.
+/// "2024-05-01T10:20:30.123Z", into seconds since the UNIX epoch.
+pub(crate) fn parse_rfc3339(value: &str) -> Option<f64> {
+    let (date, time) = value.split_once(['T', 't', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+
+    let (time, offset) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else {
+        let (time, offset) = time.split_at(time.rfind(['+', '-'])?);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        let hours: i64 = hours.parse().ok()?;
+        let minutes: i64 = minutes.parse().ok()?;
+        (time, sign * (hours * 3600 + minutes * 60))
+    };
+
+    let mut time_parts = time.splitn(3, ':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: f64 = time_parts.next()?.parse().ok()?;
+
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0.0..61.0).contains(&second)
+    {
+        return None;
+    }
+
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 - offset;
+
+    Some(seconds as f64 + second)
+}
+
+// The number of days since 1970-01-01 of the given (proleptic Gregorian)
+// date. See http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc() {
+        assert_eq!(parse_rfc3339("2024-05-01T10:20:30Z"), Some(1714558830.0));
+        assert_eq!(parse_rfc3339("2024-05-01t10:20:30z"), Some(1714558830.0));
+        assert_eq!(parse_rfc3339("2024-05-01 10:20:30Z"), Some(1714558830.0));
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0.0));
+        assert_eq!(parse_rfc3339("1969-12-31T23:59:59Z"), Some(-1.0));
+        assert_eq!(parse_rfc3339("2000-02-29T00:00:00Z"), Some(951782400.0));
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(
+            parse_rfc3339("2024-05-01T10:20:30+00:00"),
+            Some(1714558830.0)
+        );
+        assert_eq!(
+            parse_rfc3339("2024-05-01T12:20:30+02:00"),
+            Some(1714558830.0)
+        );
+        assert_eq!(
+            parse_rfc3339("2024-05-01T05:50:30-04:30"),
+            Some(1714558830.0)
+        );
+        assert_eq!(
+            parse_rfc3339("2024-05-02T00:20:30+14:00"),
+            Some(1714558830.0)
+        );
+    }
+
+    #[test]
+    fn fractional_seconds() {
+        let seconds = parse_rfc3339("2024-05-01T10:20:30.123Z").unwrap();
+        assert!((seconds - 1714558830.123).abs() < 1e-6);
+
+        let seconds = parse_rfc3339("2024-05-01T12:20:30.5+02:00").unwrap();
+        assert_eq!(seconds, 1714558830.5);
+
+        let seconds = parse_rfc3339("2024-05-01T10:20:30.123456789Z").unwrap();
+        assert!((seconds - 1714558830.123457).abs() < 1e-6);
+    }
+
+    #[test]
+    fn invalid() {
+        let invalid = [
+            "",
+            "2024-05-01",
+            "10:20:30Z",
+            "2024-05-01T10:20:30",
+            "2024-05-01T10:20Z",
+            "2024-05-01T10:20:30+02",
+            "2024-13-01T10:20:30Z",
+            "2024-00-01T10:20:30Z",
+            "2024-05-32T10:20:30Z",
+            "2024-05-01T24:20:30Z",
+            "2024-05-01T10:60:30Z",
+            "2024-05-01T10:20:61Z",
+            "2024-05-01T10:20:xxZ",
+            "yesterday",
+        ];
+
+        for value in invalid {
+            assert_eq!(parse_rfc3339(value), None, "{value}");
+        }
+    }
+}
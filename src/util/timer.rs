@@ -0,0 +1,149 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::process;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+type TimerCallback = Box<dyn FnOnce() + Send>;
+
+struct TimerEntry {
+    deadline: Instant,
+    id: u64,
+    callback: TimerCallback,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        (self.deadline, self.id) == (other.deadline, other.id)
+    }
+}
+
+impl Eq for TimerEntry {}
+
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.deadline, self.id).cmp(&(other.deadline, other.id))
+    }
+}
+
+#[derive(Default)]
+struct TimerState {
+    entries: BinaryHeap<Reverse<TimerEntry>>,
+    next_id: u64,
+    running: bool,
+    // The ID of the process that started the timer thread.
+    pid: u32,
+}
+
+#[derive(Default)]
+struct TimerShared {
+    state: Mutex<TimerState>,
+    condvar: Condvar,
+}
+
+/// Runs callbacks after a given delay in a background thread. The thread is
+/// started with the first scheduled callback and exits shortly after the
+/// timer is dropped. Pending callbacks might not be called once the timer is
+/// dropped.
+#[derive(Default)]
+pub(crate) struct Timer {
+    shared: Arc<TimerShared>,
+}
+
+impl Timer {
+    pub fn schedule(&self, delay: Duration, callback: impl FnOnce() + Send + 'static) {
+        let mut state = self.shared.state.lock().unwrap();
+
+        let id = state.next_id;
+        state.next_id += 1;
+
+        state.entries.push(Reverse(TimerEntry {
+            deadline: Instant::now() + delay,
+            id,
+            callback: Box::new(callback),
+        }));
+
+        // The timer thread doesn't exist in a forked child, so start a new
+        // one.
+        if state.running && state.pid != process::id() {
+            state.running = false;
+        }
+
+        if !state.running {
+            state.running = true;
+            state.pid = process::id();
+
+            let shared = Arc::downgrade(&self.shared);
+            let spawned = thread::Builder::new()
+                .name("daily-timer".to_string())
+                .spawn(move || run_timer(shared));
+
+            if spawned.is_err() {
+                state.running = false;
+            }
+        }
+
+        self.shared.condvar.notify_one();
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        // The timer thread only holds a weak reference, wake it up so it
+        // notices we are gone.
+        self.shared.condvar.notify_one();
+    }
+}
+
+fn run_timer(shared: Weak<TimerShared>) {
+    loop {
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+
+        let mut state = shared.state.lock().unwrap();
+
+        let now = Instant::now();
+
+        let mut expired = Vec::new();
+        while state
+            .entries
+            .peek()
+            .is_some_and(|Reverse(entry)| entry.deadline <= now)
+        {
+            if let Some(Reverse(entry)) = state.entries.pop() {
+                expired.push(entry.callback);
+            }
+        }
+
+        if expired.is_empty() {
+            // Wake up at the next deadline or, at the latest, after a while to
+            // check if the timer is still alive.
+            let wait = state
+                .entries
+                .peek()
+                .map_or(Duration::from_secs(1), |Reverse(entry)| {
+                    entry.deadline.saturating_duration_since(now)
+                })
+                .min(Duration::from_secs(1));
+
+            let _ = shared.condvar.wait_timeout(state, wait).unwrap();
+        } else {
+            // Don't hold the lock (or the timer) while running callbacks,
+            // they might schedule new callbacks or drop the timer.
+            drop(state);
+            drop(shared);
+
+            for callback in expired {
+                callback();
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+import os
+import sys
+import time
+
+import pytest
+
+from daily import CallClient, Daily
+
+pytestmark = pytest.mark.skipif(not hasattr(os, "fork"), reason="requires os.fork()")
+
+# How long to wait for the child process before assuming it hung.
+CHILD_TIMEOUT = 10.0
+
+
+def run_in_child(target):
+    pid = os.fork()
+    if pid == 0:
+        exit_code = 1
+        try:
+            target()
+            exit_code = 0
+        except BaseException as error:
+            print(f"child failed: {error!r}", file=sys.stderr)
+        finally:
+            os._exit(exit_code)
+
+    deadline = time.monotonic() + CHILD_TIMEOUT
+    while time.monotonic() < deadline:
+        waited_pid, status = os.waitpid(pid, os.WNOHANG)
+        if waited_pid == pid:
+            return os.WEXITSTATUS(status) if os.WIFEXITED(status) else -1
+        time.sleep(0.05)
+
+    os.kill(pid, 9)
+    os.waitpid(pid, 0)
+    pytest.fail("child process hung")
+
+
+@pytest.fixture
+def daily():
+    Daily.init()
+    yield
+    Daily.deinit()
+
+
+def test_parent_context_raises_in_child(daily):
+    def child():
+        calls = [
+            lambda: CallClient(),
+            lambda: Daily.create_camera_device("camera", 640, 480),
+            lambda: Daily.create_microphone_device("microphone"),
+            lambda: Daily.create_speaker_device("speaker"),
+            lambda: Daily.select_speaker_device("speaker"),
+            lambda: Daily.list_devices(),
+            lambda: Daily.destroy_device("camera"),
+        ]
+        for call in calls:
+            with pytest.raises(RuntimeError):
+                call()
+
+    assert run_in_child(child) == 0
+
+
+def test_parent_devices_raise_in_child(daily):
+    microphone = Daily.create_microphone_device("microphone", non_blocking=True)
+
+    def child():
+        with pytest.raises(RuntimeError):
+            microphone.write_frames(b"\x00\x00" * 160)
+
+    assert run_in_child(child) == 0
+
+
+def test_child_can_initialize_again(daily):
+    def child():
+        Daily.init()
+        Daily.create_camera_device("camera", 640, 480)
+        assert len(Daily.list_devices()) == 1
+        Daily.deinit()
+
+    assert run_in_child(child) == 0